@@ -1,37 +1,190 @@
-use eyre::Result;
+use eyre::{eyre, Result};
+use std::collections::{HashMap, VecDeque};
+use utils::input::InputContext;
 
-pub fn solve_one(input: &str) -> Result<String> {
-    Ok(input
-        .lines()
-        .map(line_to_num)
-        .collect::<Result<Vec<i64>>>()?
-        .into_iter()
-        .sum::<i64>()
-        .to_string())
+/// Digit characters and their values.
+pub const DIGITS: [(&str, u32); 10] = [
+    ("0", 0),
+    ("1", 1),
+    ("2", 2),
+    ("3", 3),
+    ("4", 4),
+    ("5", 5),
+    ("6", 6),
+    ("7", 7),
+    ("8", 8),
+    ("9", 9),
+];
+
+/// Digits spelled out in English.
+pub const ENGLISH: [(&str, u32); 9] = [
+    ("one", 1),
+    ("two", 2),
+    ("three", 3),
+    ("four", 4),
+    ("five", 5),
+    ("six", 6),
+    ("seven", 7),
+    ("eight", 8),
+    ("nine", 9),
+];
+
+/// Token found in a line, `position` is the byte offset of its start.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Match {
+    pub position: usize,
+    pub value: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+struct Node {
+    next: HashMap<u8, usize>,
+    // longest proper suffix of this node's prefix that is also a prefix of a token
+    fail: usize,
+    // token ending at this node, as (length in bytes, value)
+    token: Option<(usize, u32)>,
+    // nearest node on the fail chain with a token, so overlapping tokens ending here are found
+    dict: Option<usize>,
+}
+
+/// Finds the digits of a vocabulary in a line in a single pass, including overlapping tokens such
+/// as "twone", using an Aho–Corasick automaton.
+#[derive(Clone, Debug)]
+pub struct Decoder {
+    nodes: Vec<Node>,
 }
+impl Decoder {
+    /// Decoder for a vocabulary of tokens and the digit each stands for, empty tokens are ignored.
+    pub fn new<'a>(vocabulary: impl IntoIterator<Item = (&'a str, u32)>) -> Self {
+        let mut nodes = vec![Node::default()];
+        for (token, value) in vocabulary.into_iter().filter(|(t, _)| !t.is_empty()) {
+            let mut n = 0;
+            for &b in token.as_bytes() {
+                n = match nodes[n].next.get(&b) {
+                    Some(&next) => next,
+                    None => {
+                        nodes.push(Node::default());
+                        let next = nodes.len() - 1;
+                        nodes[n].next.insert(b, next);
+                        next
+                    }
+                };
+            }
+            nodes[n].token = Some((token.len(), value));
+        }
+
+        // breadth first, so the fail links of shallower nodes are set before they are followed
+        let mut queue = nodes[0].next.values().copied().collect::<VecDeque<usize>>();
+        while let Some(n) = queue.pop_front() {
+            let children = nodes[n]
+                .next
+                .iter()
+                .map(|(&b, &child)| (b, child))
+                .collect::<Vec<(u8, usize)>>();
+            for (b, child) in children {
+                let mut f = nodes[n].fail;
+                let fail = loop {
+                    if let Some(&next) = nodes[f].next.get(&b) {
+                        break next;
+                    } else if f == 0 {
+                        break 0;
+                    }
+                    f = nodes[f].fail;
+                };
+                nodes[child].fail = fail;
+                nodes[child].dict = if nodes[fail].token.is_some() {
+                    Some(fail)
+                } else {
+                    nodes[fail].dict
+                };
+                queue.push_back(child);
+            }
+        }
+        Self { nodes }
+    }
+
+    /// Decoder for digit characters only.
+    pub fn digits() -> Self {
+        Self::new(DIGITS)
+    }
+
+    /// Decoder for digit characters and digits spelled out in English.
+    pub fn english() -> Self {
+        Self::new(DIGITS.into_iter().chain(ENGLISH))
+    }
+
+    fn scan(&self, line: &str, mut on_match: impl FnMut(Match)) {
+        let mut n = 0;
+        for (i, &b) in line.as_bytes().iter().enumerate() {
+            loop {
+                if let Some(&next) = self.nodes[n].next.get(&b) {
+                    n = next;
+                    break;
+                } else if n == 0 {
+                    break;
+                }
+                n = self.nodes[n].fail;
+            }
+            let mut out = Some(n);
+            while let Some(o) = out {
+                if let Some((len, value)) = self.nodes[o].token {
+                    on_match(Match {
+                        position: i + 1 - len,
+                        value,
+                    });
+                }
+                out = self.nodes[o].dict;
+            }
+        }
+    }
+
+    /// Every token in the line, including overlapping ones, in the order they end.
+    pub fn matches(&self, line: &str) -> Vec<Match> {
+        let mut matches = vec![];
+        self.scan(line, |m| matches.push(m));
+        matches
+    }
+
+    /// Tokens starting first and last in the line, `None` if there are none. Of tokens starting at
+    /// the same position the first to end is used.
+    pub fn first_last(&self, line: &str) -> Option<(Match, Match)> {
+        let mut first_last: Option<(Match, Match)> = None;
+        self.scan(line, |m| match &mut first_last {
+            None => first_last = Some((m, m)),
+            Some((first, last)) => {
+                if m.position < first.position {
+                    *first = m;
+                }
+                if m.position > last.position {
+                    *last = m;
+                }
+            }
+        });
+        first_last
+    }
+
+    /// Calibration value of a line, the first digit followed by the last.
+    pub fn calibration_value(&self, line: &str) -> Result<u32> {
+        let (first, last) = self
+            .first_last(line)
+            .ok_or(eyre!("no digits found in line"))?;
+        Ok(first.value * 10 + last.value)
+    }
 
-fn line_to_num(l: &str) -> Result<i64> {
-    let mut l = l.trim_matches(char::is_alphabetic).to_string();
-    if l.len() > 1 {
-        l.replace_range(1..(l.len() - 1), "");
-    } else {
-        l = l.clone() + &l;
+    /// Sum of the calibration values of every line.
+    pub fn calibrate(&self, input: &str) -> Result<u32> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| self.calibration_value(l).line_context(i, l))
+            .sum()
     }
-    Ok(l.parse::<i64>()?)
 }
 
-pub fn solve_two(input: &str) -> Result<String> {
-    solve_one(&replace(input))
+pub fn solve_one(input: &str) -> Result<String> {
+    Ok(Decoder::digits().calibrate(input)?.to_string())
 }
 
-fn replace(s: &str) -> String {
-    let s = s.replace("one", "one1one");
-    let s = s.replace("two", "two2two");
-    let s = s.replace("three", "three3three");
-    let s = s.replace("four", "four4four");
-    let s = s.replace("five", "five5five");
-    let s = s.replace("six", "six6six");
-    let s = s.replace("seven", "seven7seven");
-    let s = s.replace("eight", "eight8eight");
-    s.replace("nine", "nine9nine")
+pub fn solve_two(input: &str) -> Result<String> {
+    Ok(Decoder::english().calibrate(input)?.to_string())
 }
@@ -0,0 +1,57 @@
+use day1::{Decoder, Match};
+
+fn m(position: usize, value: u32) -> Match {
+    Match { position, value }
+}
+
+#[test]
+fn overlapping_tokens() {
+    let english = Decoder::english();
+    assert_eq!(english.matches("twone"), [m(0, 2), m(2, 1)]);
+    assert_eq!(english.calibration_value("twone").unwrap(), 21);
+    assert_eq!(english.matches("eightwo"), [m(0, 8), m(4, 2)]);
+    assert_eq!(english.calibration_value("eightwo").unwrap(), 82);
+    assert_eq!(
+        english.first_last("xtwoneighthree7"),
+        Some((m(1, 2), m(14, 7)))
+    );
+    assert_eq!(english.calibration_value("zoneight234").unwrap(), 14);
+
+    // digits only, spelled out digits are ignored
+    assert_eq!(Decoder::digits().matches("twone3"), [m(5, 3)]);
+}
+
+#[test]
+fn other_vocabulary() {
+    let german = Decoder::new([
+        ("eins", 1),
+        ("zwei", 2),
+        ("drei", 3),
+        ("vier", 4),
+        ("fünf", 5),
+        ("sechs", 6),
+        ("sieben", 7),
+        ("acht", 8),
+        ("neun", 9),
+    ]);
+    assert_eq!(german.calibration_value("siebenundacht").unwrap(), 78);
+    // "drei" and "eins" overlap on "ei"
+    assert_eq!(german.matches("dreins"), [m(0, 3), m(2, 1)]);
+    // positions are byte offsets, "ü" is two bytes
+    assert_eq!(german.matches("fünfzwei"), [m(0, 5), m(5, 2)]);
+    // english words and digit characters are not in the vocabulary
+    assert!(german.first_last("one 2 three").is_none());
+    assert_eq!(german.calibrate("eins\nzweidrei\n").unwrap(), 11 + 23);
+}
+
+#[test]
+fn no_digits() {
+    let english = Decoder::english();
+    assert!(english.matches("abcdef").is_empty());
+    assert!(english.first_last("").is_none());
+    assert!(english.calibration_value("onx twx").is_err());
+
+    let err = english.calibrate("1abc2\nxyz\n").unwrap_err();
+    let location = utils::input::location(&err).unwrap();
+    assert_eq!((location.line, location.text.as_str()), (2, "xyz"));
+}
@@ -1,12 +1,39 @@
 use eyre::{eyre, Result};
+use utils::input::InputContext;
+use utils::timing;
+use utils::visualize::Frames;
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
+fn parse_input(input: &str) -> Result<Vec<Vec<char>>> {
     input
         .lines()
-        .map(|l| l.chars().collect::<Vec<char>>())
+        .enumerate()
+        .map(|(i, l)| {
+            l.chars()
+                .enumerate()
+                .map(|(j, c)| {
+                    if "|-LJ7FS.".contains(c) {
+                        Ok(c)
+                    } else {
+                        Err(eyre!("unexpected char {}", c))
+                    }
+                    .column_context(i, j, l)
+                })
+                .collect::<Result<Vec<char>>>()
+        })
         .collect()
 }
 
+// Add the input location of `pos`, in a grid with a boarder of `boarder` added around the input.
+fn pos_context<T>(
+    result: Result<T>,
+    input: &str,
+    pos: (usize, usize),
+    boarder: usize,
+) -> Result<T> {
+    let (i, j) = (pos.0.saturating_sub(boarder), pos.1.saturating_sub(boarder));
+    result.column_context(i, j, input.lines().nth(i).unwrap_or_default())
+}
+
 fn find_start(grid: &[Vec<char>]) -> Result<(usize, usize)> {
     grid.iter()
         .enumerate()
@@ -94,12 +121,13 @@ fn find_next(
                 (cur.0, cur.1 + 1)
             }
         }
-        other => eyre::bail!("unexpected char at cur {}", other),
+        other => eyre::bail!("pipe leads into {}", other),
     })
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
-    let grid = parse_input(input);
+    let grid = parse_input(input)?;
+    timing::parsed();
 
     let s = find_start(&grid)?;
 
@@ -116,11 +144,11 @@ pub fn solve_one(input: &str) -> Result<String> {
     let mut last2 = s;
 
     while cur1 != cur2 {
-        let next1 = find_next(&grid, last1, cur1)?;
+        let next1 = pos_context(find_next(&grid, last1, cur1), input, cur1, 0)?;
         last1 = cur1;
         cur1 = next1;
 
-        let next2 = find_next(&grid, last2, cur2)?;
+        let next2 = pos_context(find_next(&grid, last2, cur2), input, cur2, 0)?;
         last2 = cur2;
         cur2 = next2;
 
@@ -226,7 +254,7 @@ fn mark_l_r_p(
             }
         }
         'S' => (vec![], vec![]),
-        other => eyre::bail!("unexpected char at cur {}", other),
+        other => eyre::bail!("unexpected char {} on the loop", other),
     };
 
     l.into_iter().for_each(|l| {
@@ -244,8 +272,11 @@ fn mark_l_r_p(
     Ok(())
 }
 
-pub fn solve_two(input: &str) -> Result<String> {
-    let mut grid = parse_input(input);
+// Grid with a boarder added, with the loop marked as path(p) and the areas either side of it
+// marked as l/r, along with which of l/r is inside the loop.
+fn mark_loop(input: &str) -> Result<(Vec<Vec<char>>, char)> {
+    let mut grid = parse_input(input)?;
+    timing::parsed();
 
     // add boarder, so we dont need to worry about going out of bounds
     grid.iter_mut().for_each(|r| {
@@ -264,7 +295,7 @@ pub fn solve_two(input: &str) -> Result<String> {
     let mut cur = find_connecting(&grid, last)[0];
     let mut path = vec![last, cur];
     while cur != s {
-        let next = find_next(&grid, last, cur)?;
+        let next = pos_context(find_next(&grid, last, cur), input, cur, 1)?;
         path.push(next);
         last = cur;
         cur = next;
@@ -274,7 +305,7 @@ pub fn solve_two(input: &str) -> Result<String> {
     path.windows(2).try_for_each(|w| {
         let f = w[0];
         let t = w[1];
-        mark_l_r_p(&mut grid, &path, f, t)
+        pos_context(mark_l_r_p(&mut grid, &path, f, t), input, t, 1)
     })?;
 
     let max_i = grid.len() - 1;
@@ -315,6 +346,12 @@ pub fn solve_two(input: &str) -> Result<String> {
     })
     .ok_or(eyre!("failed to find inside char, no l/r on boarder"))?;
 
+    Ok((grid, in_char))
+}
+
+pub fn solve_two(input: &str) -> Result<String> {
+    let (grid, in_char) = mark_loop(input)?;
+
     Ok(grid
         .into_iter()
         .flat_map(|r| r.into_iter())
@@ -322,3 +359,28 @@ pub fn solve_two(input: &str) -> Result<String> {
         .count()
         .to_string())
 }
+
+/// Frames of the pipes, then the loop, then the tiles inside the loop.
+pub fn visualize(input: &str) -> Result<Frames> {
+    let pipes = parse_input(input)?;
+    let (marked, in_char) = mark_loop(input)?;
+    let width = pipes.first().map(|r| r.len()).unwrap_or_default();
+
+    // ground, pipe, loop, inside
+    let mut frames = Frames::new(width, pipes.len(), &["#222", "#666", "#f80", "#3a3"]);
+    let pipe = |r: usize, c: usize| u8::from(pipes[r].get(c).is_some_and(|&p| p != '.'));
+    frames.push(pipe);
+    // marked grid has a boarder of 1
+    let on_loop = |r: usize, c: usize| marked[r + 1][c + 1] == 'p';
+    frames.push(|r, c| if on_loop(r, c) { 2 } else { pipe(r, c) });
+    frames.push(|r, c| {
+        if on_loop(r, c) {
+            2
+        } else if marked[r + 1][c + 1] == in_char {
+            3
+        } else {
+            pipe(r, c)
+        }
+    });
+    Ok(frames)
+}
@@ -1,16 +1,25 @@
 use eyre::{eyre, Result};
 use std::collections::HashMap;
+use utils::input::InputContext;
+use utils::timing;
 
-fn parse_input(input: &str) -> Vec<(usize, usize)> {
-    input
-        .lines()
-        .enumerate()
-        .flat_map(|(i, r)| {
-            r.chars()
-                .enumerate()
-                .filter_map(move |(j, c)| if c == '#' { Some((i, j)) } else { None })
-        })
-        .collect()
+fn parse_input(input: &str) -> Result<Vec<(usize, usize)>> {
+    let width = input.lines().next().ok_or(eyre!("no first line"))?.len();
+    let mut gals = vec![];
+    for (i, l) in input.lines().enumerate() {
+        if l.len() != width {
+            return Err(eyre!("expected {} chars, the first line's length", width))
+                .line_context(i, l);
+        }
+        for (j, c) in l.chars().enumerate() {
+            match c {
+                '#' => gals.push((i, j)),
+                '.' => {}
+                _ => return Err(eyre!("unexpected char {}", c)).column_context(i, j, l),
+            }
+        }
+    }
+    Ok(gals)
 }
 
 fn expand(
@@ -78,11 +87,15 @@ fn get_total_distances(gals: Vec<(usize, usize)>) -> u64 {
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
-    let gals = expand(parse_input(input), input, 1)?;
+    let gals = parse_input(input)?;
+    timing::parsed();
+    let gals = expand(gals, input, 1)?;
     Ok(get_total_distances(gals).to_string())
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    let gals = expand(parse_input(input), input, 999_999)?;
+    let gals = parse_input(input)?;
+    timing::parsed();
+    let gals = expand(gals, input, 999_999)?;
     Ok(get_total_distances(gals).to_string())
 }
@@ -1,10 +1,9 @@
 use eyre::{eyre, Result};
-use std::collections::HashMap;
+use utils::input::InputContext;
+use utils::progress;
 
-fn parse_line(line: &str) -> Result<(Vec<Option<bool>>, Vec<u8>)> {
-    let (springs, damage_count) = line
-        .split_once(' ')
-        .ok_or(eyre!("missing space in line {}", line))?;
+fn parse_line(line: &str) -> Result<(Vec<Option<bool>>, Vec<usize>)> {
+    let (springs, damage_count) = line.split_once(' ').ok_or(eyre!("missing space"))?;
     Ok((
         springs
             .chars()
@@ -17,123 +16,252 @@ fn parse_line(line: &str) -> Result<(Vec<Option<bool>>, Vec<u8>)> {
             .collect::<Result<Vec<Option<bool>>>>()?,
         damage_count
             .split(',')
-            .map(|v| Ok(v.parse::<u8>()?))
-            .collect::<Result<Vec<u8>>>()?,
+            .map(|v| Ok(v.parse::<usize>()?))
+            .collect::<Result<Vec<usize>>>()?,
     ))
 }
 
-#[allow(clippy::type_complexity)]
-fn num_arrangements(
-    springs: &[Option<bool>],
-    cons: &[u8],
-    cur: u8,
-    memo: &mut HashMap<(Vec<Option<bool>>, Vec<u8>, u8), u128>,
-) -> u128 {
-    let sum = cons.iter().sum::<u8>();
-    let gaps = if cons.is_empty() {
-        0
-    } else {
-        cons.len() as u8 - 1
-    };
-    if springs.len() < (sum + gaps - cur).into() {
-        // not enough springs left
-        return 0;
+/// Ways of placing runs of filled cells into a line of cells, `Some(true)` cells must be filled,
+/// `Some(false)` cells must be empty and `None` cells can be either.
+///
+/// Counts are kept in tables indexed by `(cell, run)` so every sub problem is only computed once,
+/// counts saturate rather than overflow for very long lines. Zero length runs, such as the `0`
+/// clue for an empty nonogram line, are dropped as they place no cells.
+struct Arrangements<'a> {
+    cells: &'a [Option<bool>],
+    runs: Vec<usize>,
+    // number of empty only cells before each index, to check a run fits in O(1)
+    empty_before: Vec<usize>,
+    // ways to fill cells[i..] with runs[k..], given cell i - 1 is empty
+    suffix: Vec<u128>,
+}
+impl<'a> Arrangements<'a> {
+    fn new(cells: &'a [Option<bool>], runs: &[usize]) -> Self {
+        let runs = runs
+            .iter()
+            .copied()
+            .filter(|&r| r > 0)
+            .collect::<Vec<usize>>();
+        let mut empty_before = vec![0; cells.len() + 1];
+        for (i, c) in cells.iter().enumerate() {
+            empty_before[i + 1] = empty_before[i] + usize::from(*c == Some(false));
+        }
+        let run_count = runs.len();
+        let mut arr = Self {
+            cells,
+            runs,
+            empty_before,
+            suffix: vec![0; (cells.len() + 1) * (run_count + 1)],
+        };
+
+        let n = cells.len();
+        let end = arr.idx(n, run_count);
+        arr.suffix[end] = 1;
+        for i in (0..n).rev() {
+            for k in 0..=run_count {
+                let mut ways = 0_u128;
+                if cells[i] != Some(true) {
+                    ways = ways.saturating_add(arr.suffix[arr.idx(i + 1, k)]);
+                }
+                if let Some(next) = arr.place(i, k) {
+                    ways = ways.saturating_add(arr.suffix[arr.idx(next, k + 1)]);
+                }
+                let idx = arr.idx(i, k);
+                arr.suffix[idx] = ways;
+            }
+        }
+        arr
     }
 
-    if let Some(r) = memo.get(&(springs.to_vec(), cons.to_vec(), cur)) {
-        return *r;
+    fn idx(&self, i: usize, k: usize) -> usize {
+        i * (self.runs.len() + 1) + k
     }
 
-    let r = if cur != 0 {
-        // have consecutive count, check if next sping fits requirements
-        if cons[0] == cur {
-            // end of consecutive count, require not damaged
-            if springs.is_empty() {
-                // no more springs, check no more cons
-                if cons.len() == 1 {
-                    // no more needed, finish here
-                    1
-                } else {
-                    // more springs needed
-                    0
-                }
-            } else if !springs[0].unwrap_or(false) {
-                // next spring meets required not damaged
-                num_arrangements(&springs[1..], &cons[1..], 0, memo)
-            } else {
-                // next spring fails requirement
-                0
-            }
+    /// If run `k` can start at cell `i`, the cell after it and its trailing empty cell.
+    fn place(&self, i: usize, k: usize) -> Option<usize> {
+        let end = i + self.runs.get(k)?;
+        if end > self.cells.len()
+            || self.empty_before[end] != self.empty_before[i]
+            || self.cells.get(end) == Some(&Some(true))
+        {
+            None
         } else {
-            // midding of consecutive count, require damaged
-            if springs.is_empty() {
-                0
-            } else if springs[0].unwrap_or(true) {
-                // next spring meets requried damanged
-                num_arrangements(&springs[1..], cons, cur + 1, memo)
-            } else {
-                // next spring fails requirement
-                0
-            }
+            Some((end + 1).min(self.cells.len()))
         }
-    } else {
-        // no consecutive count
-        match (springs.is_empty(), cons.is_empty()) {
-            (true, true) => {
-                // end springs and no more required
-                1
-            }
-            (true, false) => {
-                // end springs and but more required
-                0
+    }
+
+    fn count(&self) -> u128 {
+        self.suffix[0]
+    }
+
+    fn enumerate(&self, i: usize, k: usize, cur: &mut Vec<bool>, all: &mut Vec<Vec<bool>>) {
+        if i == self.cells.len() {
+            all.push(cur.clone());
+            return;
+        }
+        if self.cells[i] != Some(true) && self.suffix[self.idx(i + 1, k)] > 0 {
+            cur.push(false);
+            self.enumerate(i + 1, k, cur, all);
+            cur.pop();
+        }
+        if let Some(next) = self.place(i, k) {
+            if self.suffix[self.idx(next, k + 1)] > 0 {
+                let len = cur.len();
+                cur.resize(len + self.runs[k], true);
+                cur.resize(len + next - i, false);
+                self.enumerate(next, k + 1, cur, all);
+                cur.truncate(len);
             }
-            (false, true) => {
-                // still have springs, but must be not damanged
-                if !springs[0].unwrap_or(false) {
-                    num_arrangements(&springs[1..], cons, 0, memo)
-                } else {
-                    0
+        }
+    }
+
+    fn forced(&self) -> Option<Vec<Option<bool>>> {
+        if self.count() == 0 {
+            return None;
+        }
+        let n = self.cells.len();
+
+        // walk forward through every reachable (cell, run) state that can still finish,
+        // marking which cells can be empty and which can be filled
+        let mut prefix = vec![false; self.suffix.len()];
+        prefix[self.idx(0, 0)] = true;
+        let mut can_empty = vec![false; n];
+        let mut fill_diff = vec![0_i64; n + 1];
+        for i in 0..n {
+            for k in 0..=self.runs.len() {
+                if !prefix[self.idx(i, k)] {
+                    continue;
                 }
-            }
-            (false, false) => match springs[0] {
-                Some(true) => num_arrangements(&springs[1..], cons, 1, memo),
-                Some(false) => num_arrangements(&springs[1..], cons, 0, memo),
-                None => {
-                    num_arrangements(&springs[1..], cons, 0, memo)
-                        + num_arrangements(&springs[1..], cons, 1, memo)
+                if self.cells[i] != Some(true) && self.suffix[self.idx(i + 1, k)] > 0 {
+                    can_empty[i] = true;
+                    let idx = self.idx(i + 1, k);
+                    prefix[idx] = true;
+                }
+                if let Some(next) = self.place(i, k) {
+                    if self.suffix[self.idx(next, k + 1)] > 0 {
+                        let end = i + self.runs[k];
+                        fill_diff[i] += 1;
+                        fill_diff[end] -= 1;
+                        if end < n {
+                            can_empty[end] = true;
+                        }
+                        let idx = self.idx(next, k + 1);
+                        prefix[idx] = true;
+                    }
                 }
-            },
+            }
+        }
+
+        let mut fills = 0;
+        Some(
+            (0..n)
+                .map(|i| {
+                    fills += fill_diff[i];
+                    match (fills > 0, can_empty[i]) {
+                        (true, false) => Some(true),
+                        (false, true) => Some(false),
+                        _ => None,
+                    }
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Number of ways runs of filled cells, in order, can be placed in a line of known and unknown
+/// cells. Saturates at `u128::MAX`.
+pub fn count_arrangements(cells: &[Option<bool>], runs: &[usize]) -> u128 {
+    Arrangements::new(cells, runs).count()
+}
+
+/// Every way runs of filled cells can be placed in a line, as filled/empty cells. The number of
+/// arrangements can grow very quickly, check `count_arrangements` first for long lines.
+pub fn arrangements(cells: &[Option<bool>], runs: &[usize]) -> Vec<Vec<bool>> {
+    let mut all = vec![];
+    Arrangements::new(cells, runs).enumerate(0, 0, &mut Vec::with_capacity(cells.len()), &mut all);
+    all
+}
+
+/// Cells that are the same in every arrangement of the line, `None` where it differs between
+/// arrangements. Returns `None` if the runs cannot fit the line at all.
+pub fn forced_cells(cells: &[Option<bool>], runs: &[usize]) -> Option<Vec<Option<bool>>> {
+    Arrangements::new(cells, runs).forced()
+}
+
+/// Solve a nonogram puzzle from its row and column runs. Each line is solved in turn until no more
+/// cells are forced, then guesses are made on any remaining unknown cell.
+pub fn solve_nonogram(rows: &[Vec<usize>], cols: &[Vec<usize>]) -> Option<Vec<Vec<bool>>> {
+    solve_grid(vec![vec![None; cols.len()]; rows.len()], rows, cols)
+}
+
+fn solve_grid(
+    mut grid: Vec<Vec<Option<bool>>>,
+    rows: &[Vec<usize>],
+    cols: &[Vec<usize>],
+) -> Option<Vec<Vec<bool>>> {
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for (row, runs) in grid.iter_mut().zip(rows) {
+            let forced = forced_cells(row, runs)?;
+            changed |= *row != forced;
+            *row = forced;
         }
-    };
+        for (j, runs) in cols.iter().enumerate() {
+            let col = grid.iter().map(|r| r[j]).collect::<Vec<Option<bool>>>();
+            let forced = forced_cells(&col, runs)?;
+            changed |= col != forced;
+            grid.iter_mut().zip(forced).for_each(|(r, c)| r[j] = c);
+        }
+    }
 
-    memo.insert((springs.to_vec(), cons.to_vec(), cur), r);
-    r
+    let unknown = grid
+        .iter()
+        .enumerate()
+        .find_map(|(i, r)| r.iter().position(Option::is_none).map(|j| (i, j)));
+    if let Some((i, j)) = unknown {
+        [true, false].into_iter().find_map(|guess| {
+            let mut g = grid.clone();
+            g[i][j] = Some(guess);
+            solve_grid(g, rows, cols)
+        })
+    } else {
+        grid.into_iter()
+            .map(|r| r.into_iter().collect::<Option<Vec<bool>>>())
+            .collect()
+    }
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
-    let mut memo = HashMap::new();
+    let rows = input.lines().count() as u64;
     Ok(input
         .lines()
-        .map(|l| {
-            parse_line(l).map(|(springs, cons)| num_arrangements(&springs, &cons, 0, &mut memo))
+        .enumerate()
+        .map(|(i, l)| {
+            progress::report(i as u64, Some(rows));
+            parse_line(l)
+                .line_context(i, l)
+                .map(|(springs, cons)| count_arrangements(&springs, &cons))
         })
         .sum::<Result<u128>>()?
         .to_string())
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    let mut memo = HashMap::new();
+    let rows = input.lines().count() as u64;
     Ok(input
         .lines()
-        .map(|l| {
-            parse_line(l).map(|(springs, cons)| {
+        .enumerate()
+        .map(|(i, l)| {
+            progress::report(i as u64, Some(rows));
+            parse_line(l).line_context(i, l).map(|(springs, cons)| {
                 let mut s = springs.clone();
                 for _ in 0..4 {
                     s.push(None);
                     s.append(&mut springs.clone());
                 }
                 let cs = cons.repeat(5);
-                num_arrangements(&s, &cs, 0, &mut memo)
+                count_arrangements(&s, &cs)
             })
         })
         .sum::<Result<u128>>()?
@@ -0,0 +1,81 @@
+fn cells(line: &str) -> Vec<Option<bool>> {
+    line.chars()
+        .map(|c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn example_counts() {
+    for (line, runs, one, two) in [
+        ("???.###", vec![1, 1, 3], 1, 1),
+        (".??..??...?##.", vec![1, 1, 3], 4, 16384),
+        ("?#?#?#?#?#?#?#?", vec![1, 3, 1, 6], 1, 1),
+        ("????.#...#...", vec![4, 1, 1], 1, 16),
+        ("????.######..#####.", vec![1, 6, 5], 4, 2500),
+        ("?###????????", vec![3, 2, 1], 10, 506250),
+    ] {
+        assert_eq!(
+            day12::count_arrangements(&cells(line), &runs),
+            one,
+            "{line}"
+        );
+        let unfolded = [line; 5].join("?");
+        assert_eq!(
+            day12::count_arrangements(&cells(&unfolded), &runs.repeat(5)),
+            two,
+            "{line} unfolded"
+        );
+        assert_eq!(day12::arrangements(&cells(line), &runs).len() as u128, one);
+    }
+}
+
+#[test]
+fn zero_runs_place_nothing() {
+    assert_eq!(day12::count_arrangements(&cells(".."), &[0]), 1);
+    assert_eq!(day12::count_arrangements(&cells("??"), &[0]), 1);
+    assert_eq!(day12::count_arrangements(&cells("#?"), &[0]), 0);
+    assert_eq!(day12::forced_cells(&cells("??"), &[0]), Some(cells("..")));
+}
+
+#[test]
+fn forced() {
+    assert_eq!(
+        day12::forced_cells(&cells("????"), &[3]),
+        Some(vec![None, Some(true), Some(true), None])
+    );
+    assert_eq!(
+        day12::forced_cells(&cells("???.###"), &[1, 1, 3]),
+        Some(cells("#.#.###"))
+    );
+    assert_eq!(day12::forced_cells(&cells("#"), &[2]), None);
+}
+
+fn picture(rows: &[&str]) -> Vec<Vec<bool>> {
+    rows.iter()
+        .map(|r| r.chars().map(|c| c == '#').collect())
+        .collect()
+}
+
+#[test]
+fn nonogram() {
+    let rows = vec![vec![3], vec![1, 1], vec![5], vec![1, 1], vec![1, 1]];
+    let cols = vec![vec![4], vec![1, 1], vec![1, 1], vec![1, 1], vec![4]];
+    assert_eq!(
+        day12::solve_nonogram(&rows, &cols),
+        Some(picture(&[".###.", "#...#", "#####", "#...#", "#...#"]))
+    );
+}
+
+#[test]
+fn nonogram_with_empty_line() {
+    let rows = vec![vec![1, 1], vec![0], vec![1]];
+    let cols = vec![vec![1], vec![1], vec![1]];
+    assert_eq!(
+        day12::solve_nonogram(&rows, &cols),
+        Some(picture(&["#.#", "...", ".#."]))
+    );
+}
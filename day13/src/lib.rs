@@ -1,113 +1,152 @@
 use eyre::{eyre, Result};
+use utils::input::InputContext;
 
-fn parse_note(note: &str) -> Vec<Vec<char>> {
+/// Parse a note of `.` ash and `#` rocks, `first_line` is the index of its first line in the
+/// input.
+pub fn parse_note(note: &str, first_line: usize) -> Result<Vec<Vec<char>>> {
+    let width = note.lines().next().map_or(0, str::len);
     note.lines()
-        .map(|l| l.chars().collect::<Vec<char>>())
+        .enumerate()
+        .map(|(i, l)| {
+            if l.len() != width {
+                return Err(eyre!(
+                    "expected {} chars, the note's first line's length",
+                    width
+                ))
+                .line_context(first_line + i, l);
+            }
+            l.chars()
+                .enumerate()
+                .map(|(j, c)| {
+                    match c {
+                        '.' | '#' => Ok(c),
+                        _ => Err(eyre!("unexpected char {}", c)),
+                    }
+                    .column_context(first_line + i, j, l)
+                })
+                .collect::<Result<Vec<char>>>()
+        })
         .collect()
 }
 
-fn find_reflection(note: &[Vec<char>]) -> Option<Reflection> {
-    fn find(note: &[Vec<char>]) -> Option<usize> {
-        (0..note.len() - 1).find(|&i| {
-            for s in 0..=(i).min(note.len() - i - 2) {
-                if note[i - s] != note[i + 1 + s] {
-                    return false;
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Axis {
+    Vertical,
+    Horizontal,
+}
+
+/// Line of reflection in a note, the mirror sits between `line` and `line + 1` (rows for
+/// horizontal, columns for vertical). `smudges` are the `(row, col)` cells on the top/left side of
+/// the mirror that differ from their reflection.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Reflection {
+    pub axis: Axis,
+    pub line: usize,
+    pub smudges: Vec<(usize, usize)>,
+}
+impl Reflection {
+    /// Columns left of a vertical mirror, or 100 times the rows above a horizontal mirror.
+    pub fn summary(&self) -> u64 {
+        match self.axis {
+            Axis::Vertical => 1 + self.line as u64,
+            Axis::Horizontal => 100 * (1 + self.line as u64),
+        }
+    }
+}
+
+/// Find the first line of reflection with exactly `smudges` cells that differ from their
+/// reflection, checking horizontal lines before vertical.
+pub fn find_reflection(note: &[Vec<char>], smudges: usize) -> Option<Reflection> {
+    fn find(
+        len: usize,
+        smudges: usize,
+        diffs: impl Fn(usize, usize) -> Vec<(usize, usize)>,
+    ) -> Option<(usize, Vec<(usize, usize)>)> {
+        (0..len.saturating_sub(1)).find_map(|i| {
+            let mut found = vec![];
+            for s in 0..=i.min(len - i - 2) {
+                found.extend(diffs(i - s, i + 1 + s));
+                if found.len() > smudges {
+                    return None;
                 }
             }
-            true
+            (found.len() == smudges).then_some((i, found))
         })
     }
 
-    find(note)
-        .map(|r| Reflection::Horizontal(r, note.len()))
-        .or_else(|| {
-            let transpose = (0..note[0].len())
-                .map(|note_j| {
-                    (0..note.len())
-                        .map(|note_i| note[note_i][note_j])
-                        .collect::<Vec<char>>()
-                })
-                .collect::<Vec<Vec<char>>>();
-            find(&transpose).map(|r| Reflection::Verticle(r, transpose.len()))
+    let width = note.first()?.len();
+    find(note.len(), smudges, |a, b| {
+        (0..width)
+            .filter(|&j| note[a][j] != note[b][j])
+            .map(|j| (a, j))
+            .collect()
+    })
+    .map(|(line, smudges)| Reflection {
+        axis: Axis::Horizontal,
+        line,
+        smudges,
+    })
+    .or_else(|| {
+        find(width, smudges, |a, b| {
+            (0..note.len())
+                .filter(|&i| note[i][a] != note[i][b])
+                .map(|i| (i, a))
+                .collect()
+        })
+        .map(|(line, smudges)| Reflection {
+            axis: Axis::Vertical,
+            line,
+            smudges,
         })
+    })
 }
 
-fn find_reflection_with_smudge(note: &[Vec<char>]) -> Option<Reflection> {
-    fn find(note: &[Vec<char>]) -> Option<usize> {
-        (0..note.len() - 1).find(|&i| {
-            let mut mismatch = None;
-            for s in 0..=(i).min(note.len() - i - 2) {
-                if note[i - s] != note[i + 1 + s] {
-                    if mismatch.is_some() {
-                        return false;
-                    } else {
-                        mismatch = Some(((i - s), (i + 1 + s)));
-                    }
-                }
-            }
-            if let Some((r1, r2)) = mismatch {
-                (0..note[0].len())
-                    .filter(|&i| note[r1][i] != note[r2][i])
-                    .count()
-                    == 1
+/// Draw the note with its mirror line, `-` between rows or `|` between columns, and smudges
+/// marked with `*`.
+pub fn render(note: &[Vec<char>], reflection: &Reflection) -> String {
+    let mut lines = vec![];
+    for (i, row) in note.iter().enumerate() {
+        let mut line = String::new();
+        for (j, c) in row.iter().enumerate() {
+            line.push(if reflection.smudges.contains(&(i, j)) {
+                '*'
             } else {
-                false
+                *c
+            });
+            if reflection.axis == Axis::Vertical && j == reflection.line {
+                line.push('|');
             }
-        })
+        }
+        lines.push(line);
+        if reflection.axis == Axis::Horizontal && i == reflection.line {
+            lines.push("-".repeat(row.len()));
+        }
     }
-
-    find(note)
-        .map(|r| Reflection::Horizontal(r, note.len()))
-        .or_else(|| {
-            let transpose = (0..note[0].len())
-                .map(|note_j| {
-                    (0..note.len())
-                        .map(|note_i| note[note_i][note_j])
-                        .collect::<Vec<char>>()
-                })
-                .collect::<Vec<Vec<char>>>();
-            find(&transpose).map(|r| Reflection::Verticle(r, transpose.len()))
-        })
+    lines.join("\n")
 }
 
-#[derive(Debug)]
-enum Reflection {
-    Verticle(usize, usize),
-    Horizontal(usize, usize),
+fn summarise(input: &str, smudges: usize) -> Result<u64> {
+    let mut first_line = 0;
+    let mut total = 0;
+    for note in input.split("\n\n") {
+        let parsed = parse_note(note, first_line)?;
+        total += find_reflection(&parsed, smudges)
+            .map(|r| r.summary())
+            .ok_or(eyre!(
+                "no reflection with {} smudges found in note",
+                smudges
+            ))
+            .line_context(first_line, note.lines().next().unwrap_or_default())?;
+        // notes are separated by a blank line
+        first_line += note.lines().count() + 1;
+    }
+    Ok(total)
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
-    Ok(input
-        .split("\n\n")
-        .map(parse_note)
-        .enumerate()
-        .map(|(i, note)| {
-            Ok(
-                match find_reflection(&note).ok_or(eyre!("no reflection found in note {}", i))? {
-                    Reflection::Verticle(r, _) => 1 + r as u64,
-                    Reflection::Horizontal(r, _) => 100 * (1 + r as u64),
-                },
-            )
-        })
-        .sum::<Result<u64>>()?
-        .to_string())
+    Ok(summarise(input, 0)?.to_string())
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    Ok(input
-        .split("\n\n")
-        .map(parse_note)
-        .enumerate()
-        .map(|(i, note)| {
-            Ok(
-                match find_reflection_with_smudge(&note)
-                    .ok_or(eyre!("no reflection with smudge found in note {}", i))?
-                {
-                    Reflection::Verticle(r, _) => 1 + r as u64,
-                    Reflection::Horizontal(r, _) => 100 * (1 + r as u64),
-                },
-            )
-        })
-        .sum::<Result<u64>>()?
-        .to_string())
+    Ok(summarise(input, 1)?.to_string())
 }
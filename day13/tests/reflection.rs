@@ -0,0 +1,97 @@
+use day13::{Axis, Reflection};
+
+const FIRST: &str = "#.##..##.
+..#.##.#.
+##......#
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#.";
+
+const SECOND: &str = "#...##..#
+#....#..#
+..##..###
+#####.##.
+#####.##.
+..##..###
+#....#..#";
+
+fn reflection(note: &str, first_line: usize, smudges: usize) -> Option<Reflection> {
+    day13::find_reflection(&day13::parse_note(note, first_line).unwrap(), smudges)
+}
+
+fn at(axis: Axis, line: usize, smudges: &[(usize, usize)]) -> Option<Reflection> {
+    Some(Reflection {
+        axis,
+        line,
+        smudges: smudges.to_vec(),
+    })
+}
+
+#[test]
+fn example() {
+    assert_eq!(reflection(FIRST, 0, 0), at(Axis::Vertical, 4, &[]));
+    assert_eq!(reflection(SECOND, 8, 0), at(Axis::Horizontal, 3, &[]));
+    assert_eq!(reflection(FIRST, 0, 1), at(Axis::Horizontal, 2, &[(0, 0)]));
+    assert_eq!(reflection(SECOND, 8, 1), at(Axis::Horizontal, 0, &[(0, 4)]));
+    assert_eq!(reflection(FIRST, 0, 0).unwrap().summary(), 5);
+    assert_eq!(reflection(SECOND, 8, 1).unwrap().summary(), 100);
+
+    let input = format!("{}\n\n{}\n", FIRST, SECOND);
+    assert_eq!(day13::solve_one(&input).unwrap(), "405");
+    assert_eq!(day13::solve_two(&input).unwrap(), "400");
+}
+
+#[test]
+fn more_smudges() {
+    assert_eq!(
+        reflection(FIRST, 0, 2),
+        at(Axis::Vertical, 0, &[(0, 0), (6, 0)])
+    );
+    assert_eq!(reflection(FIRST, 0, 3), None);
+    assert_eq!(
+        reflection(SECOND, 8, 2),
+        at(Axis::Vertical, 6, &[(2, 5), (5, 5)])
+    );
+    assert_eq!(
+        reflection(SECOND, 8, 3),
+        at(Axis::Vertical, 0, &[(0, 0), (1, 0), (6, 0)])
+    );
+    assert_eq!(reflection("#.\n.#", 0, 0), None);
+    assert_eq!(reflection("", 0, 0), None);
+}
+
+#[test]
+fn render() {
+    let note = day13::parse_note(FIRST, 0).unwrap();
+    let render = |smudges| day13::render(&note, &day13::find_reflection(&note, smudges).unwrap());
+    assert_eq!(
+        render(0),
+        "#.##.|.##.
+..#.#|#.#.
+##...|...#
+##...|...#
+..#.#|#.#.
+..##.|.##.
+#.#.#|#.#."
+    );
+    assert_eq!(
+        render(1),
+        "*.##..##.
+..#.##.#.
+##......#
+---------
+##......#
+..#.##.#.
+..##..##.
+#.#.##.#."
+    );
+}
+
+#[test]
+fn parse_errors() {
+    let err = day13::parse_note("#.#\n.x.", 8).unwrap_err();
+    let location = utils::input::location(&err).unwrap();
+    assert_eq!((location.line, location.column), (10, Some(2)));
+    assert!(day13::parse_note("#.#\n.#", 0).is_err());
+}
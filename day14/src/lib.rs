@@ -1,7 +1,10 @@
 use eyre::{eyre, Result};
 use std::collections::hash_map::DefaultHasher;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::hash::{Hash, Hasher};
+use utils::input::InputContext;
+use utils::timing;
+use utils::visualize::Frames;
 
 #[derive(Hash, PartialEq)]
 enum Rock {
@@ -12,13 +15,18 @@ enum Rock {
 fn parse_input(input: &str) -> Result<Vec<Vec<Option<Rock>>>> {
     input
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(i, l)| {
             l.chars()
-                .map(|c| match c {
-                    '#' => Ok(Some(Rock::Square)),
-                    'O' => Ok(Some(Rock::Round)),
-                    '.' => Ok(None),
-                    _ => Err(eyre!("unexpected char {}", c)),
+                .enumerate()
+                .map(|(j, c)| {
+                    match c {
+                        '#' => Ok(Some(Rock::Square)),
+                        'O' => Ok(Some(Rock::Round)),
+                        '.' => Ok(None),
+                        _ => Err(eyre!("unexpected char {}", c)),
+                    }
+                    .column_context(i, j, l)
                 })
                 .collect::<Result<Vec<Option<Rock>>>>()
         })
@@ -88,6 +96,7 @@ fn spin(platform: &mut [Vec<Option<Rock>>]) {
 
 pub fn solve_one(input: &str) -> Result<String> {
     let platform = parse_input(input)?;
+    timing::parsed();
     let max_i = platform.len();
     Ok((0..platform[0].len())
         .map(|j| {
@@ -112,6 +121,7 @@ pub fn solve_one(input: &str) -> Result<String> {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let mut platform = parse_input(input)?;
+    timing::parsed();
     let mut seen_map = HashMap::new();
     let mut seen_i = None;
     let mut i = 0;
@@ -148,3 +158,40 @@ pub fn solve_two(input: &str) -> Result<String> {
         .sum::<u64>()
         .to_string())
 }
+
+/// Frames of the platform, after each tilt of the first spin cycle, then after each spin cycle
+/// until the platform repeats.
+pub fn visualize(input: &str) -> Result<Frames> {
+    let mut platform = parse_input(input)?;
+    let width = platform.first().map(|r| r.len()).unwrap_or_default();
+
+    // empty, square, round
+    let mut frames = Frames::new(width, platform.len(), &["#222", "#888", "#fc3"]);
+    let push = |frames: &mut Frames, platform: &[Vec<Option<Rock>>]| {
+        frames.push(|i, j| match platform[i][j] {
+            None => 0,
+            Some(Rock::Square) => 1,
+            Some(Rock::Round) => 2,
+        })
+    };
+    push(&mut frames, &platform);
+    for dir in [Dir::North, Dir::West, Dir::South, Dir::East] {
+        tilt(&mut platform, dir);
+        push(&mut frames, &platform);
+    }
+
+    let mut seen = HashSet::new();
+    while frames.frames.len() < utils::visualize::MAX_FRAMES {
+        let hash = {
+            let mut h = DefaultHasher::new();
+            platform.hash(&mut h);
+            h.finish()
+        };
+        if !seen.insert(hash) {
+            break;
+        }
+        spin(&mut platform);
+        push(&mut frames, &platform);
+    }
+    Ok(frames)
+}
@@ -1,4 +1,5 @@
-use eyre::Result;
+use eyre::{eyre, Result};
+use utils::input::InputContext;
 
 fn hash(s: &str) -> u64 {
     let mut h = 0;
@@ -28,24 +29,31 @@ pub fn solve_one(input: &str) -> Result<String> {
 pub fn solve_two(input: &str) -> Result<String> {
     let mut boxes: Vec<Vec<(&str, u64)>> = vec![vec![]; 256];
     let input = input.replace('\n', "");
-    input.split(',').try_for_each(|s| {
+    let mut column = 0;
+    for s in input.split(',') {
         if let Some((label, lens)) = s.split_once('=') {
             let b = &mut boxes[hash(label) as usize];
-            let lens = lens.parse::<u64>()?;
+            let lens = lens
+                .parse::<u64>()
+                .map_err(eyre::Report::from)
+                .column_context(0, column + label.len() + 1, &input)?;
             if let Some(i) = find_in_box(b, label) {
                 b[i] = (label, lens);
             } else {
                 b.push((label, lens));
             }
         } else {
-            let label = s.trim_end_matches('-');
+            let label = s
+                .strip_suffix('-')
+                .ok_or(eyre!("expected '=' or '-' operation"))
+                .column_context(0, column, &input)?;
             let b = &mut boxes[hash(label) as usize];
             if let Some(i) = find_in_box(b, label) {
                 b.remove(i);
             }
         }
-        Ok::<(), eyre::Error>(())
-    })?;
+        column += s.len() + 1;
+    }
 
     Ok(boxes
         .iter()
@@ -1,4 +1,8 @@
 use eyre::{eyre, Result};
+use utils::input::InputContext;
+use utils::progress;
+use utils::timing;
+use utils::visualize::Frames;
 
 #[derive(Clone, Debug)]
 enum Contraption {
@@ -18,26 +22,33 @@ enum Dir {
 fn parse_input(input: &str) -> Result<Vec<Vec<Option<Contraption>>>> {
     input
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(i, l)| {
             l.chars()
-                .map(|c| match c {
-                    '.' => Ok(None),
-                    '-' => Ok(Some(Contraption::HSplit)),
-                    '|' => Ok(Some(Contraption::VSplit)),
-                    '/' => Ok(Some(Contraption::UpLeftMirror)),
-                    '\\' => Ok(Some(Contraption::UpRightMirror)),
-                    _ => Err(eyre!("unexpected char {}", c)),
+                .enumerate()
+                .map(|(j, c)| {
+                    match c {
+                        '.' => Ok(None),
+                        '-' => Ok(Some(Contraption::HSplit)),
+                        '|' => Ok(Some(Contraption::VSplit)),
+                        '/' => Ok(Some(Contraption::UpLeftMirror)),
+                        '\\' => Ok(Some(Contraption::UpRightMirror)),
+                        _ => Err(eyre!("unexpected char {}", c)),
+                    }
+                    .column_context(i, j, l)
                 })
                 .collect::<Result<Vec<Option<Contraption>>>>()
         })
         .collect()
 }
 
+// `on_enter` is called with each newly energised position, in the order the beams reach them.
 fn light(
     grid: &[Vec<Option<Contraption>>],
     entered: &mut [Vec<Vec<Dir>>],
     pos: (usize, usize),
     dir: Dir,
+    on_enter: &mut impl FnMut((usize, usize)),
 ) {
     use Contraption::*;
     use Dir::*;
@@ -49,6 +60,9 @@ fn light(
                 cur = None;
                 continue;
             }
+            if entered[pos.0][pos.1].is_empty() {
+                on_enter(pos);
+            }
             entered[pos.0][pos.1].push(dir.clone());
 
             let mut next_dirs = match &grid[pos.0][pos.1] {
@@ -126,7 +140,7 @@ fn light_recursive(
     };
     next_dirs.into_iter().for_each(|d| {
         if let Some(next) = next_pos(grid, pos, &d) {
-            light(grid, entered, next, d);
+            light(grid, entered, next, d, &mut |_| {});
         }
     });
 }
@@ -170,8 +184,9 @@ fn next_pos(
 
 pub fn solve_one(input: &str) -> Result<String> {
     let grid = parse_input(input)?;
+    timing::parsed();
     let mut entered: Vec<Vec<Vec<Dir>>> = vec![vec![vec![]; grid[0].len()]; grid.len()];
-    light(&grid, &mut entered, (0, 0), Dir::Right);
+    light(&grid, &mut entered, (0, 0), Dir::Right, &mut |_| {});
     Ok(entered
         .into_iter()
         .flat_map(|v| v.into_iter())
@@ -182,6 +197,10 @@ pub fn solve_one(input: &str) -> Result<String> {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let grid = parse_input(input)?;
+    timing::parsed();
+    // a start for every edge tile on each side of the grid
+    let starts = 2 * (grid.len() + grid[0].len()) as u64;
+    let mut max = 0;
     Ok(vec![
         (0..grid.len())
             .flat_map(|i| {
@@ -196,16 +215,50 @@ pub fn solve_two(input: &str) -> Result<String> {
     ]
     .into_iter()
     .flatten()
-    .map(|(pos, dir)| {
+    .enumerate()
+    .map(|(i, (pos, dir))| {
         let mut entered: Vec<Vec<Vec<Dir>>> = vec![vec![vec![]; grid[0].len()]; grid.len()];
-        light(&grid, &mut entered, pos, dir);
-        entered
+        light(&grid, &mut entered, pos, dir, &mut |_| {});
+        let energised = entered
             .into_iter()
             .flat_map(|v| v.into_iter())
             .filter(|dirs| !dirs.is_empty())
-            .count()
+            .count();
+        max = max.max(energised);
+        progress::report_value(i as u64 + 1, Some(starts), || {
+            format!("max energised {}", max)
+        });
+        energised
     })
     .max()
     .ok_or(eyre!("no max found"))?
     .to_string())
 }
+
+/// Frames of the beam from the top left energising tiles for part one.
+pub fn visualize(input: &str) -> Result<Frames> {
+    const STEPS: usize = 100;
+
+    let grid = parse_input(input)?;
+    let width = grid.first().map(|r| r.len()).unwrap_or_default();
+    let mut entered: Vec<Vec<Vec<Dir>>> = vec![vec![vec![]; width]; grid.len()];
+    let mut order = vec![];
+    if width > 0 {
+        light(&grid, &mut entered, (0, 0), Dir::Right, &mut |pos| {
+            order.push(pos)
+        });
+    }
+
+    // empty, contraption, energised, energised contraption
+    let mut frames = Frames::new(width, grid.len(), &["#222", "#888", "#fc3", "#f80"]);
+    let mut energised = vec![vec![false; width]; grid.len()];
+    let push = |frames: &mut Frames, energised: &[Vec<bool>]| {
+        frames.push(|i, j| u8::from(grid[i][j].is_some()) + 2 * u8::from(energised[i][j]))
+    };
+    push(&mut frames, &energised);
+    for chunk in order.chunks(order.len().div_ceil(STEPS).max(1)) {
+        chunk.iter().for_each(|&(i, j)| energised[i][j] = true);
+        push(&mut frames, &energised);
+    }
+    Ok(frames)
+}
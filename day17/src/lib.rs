@@ -1,276 +1,187 @@
-use eyre::{eyre, Result};
-use std::collections::{HashSet, VecDeque};
-
-fn parse_input(input: &str) -> Vec<Vec<u64>> {
+use eyre::{bail, eyre, Result};
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use utils::input::InputContext;
+use utils::timing;
+use utils::visualize::Frames;
+
+pub fn parse_input(input: &str) -> Result<Vec<Vec<u64>>> {
     input
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(i, l)| {
             l.chars()
-                .map(|c| c as u64 - '0' as u64)
-                .collect::<Vec<u64>>()
+                .enumerate()
+                .map(|(j, c)| {
+                    {
+                        c.to_digit(10)
+                            .map(u64::from)
+                            .ok_or(eyre!("unexpected char {}", c))
+                    }
+                    .column_context(i, j, l)
+                })
+                .collect::<Result<Vec<u64>>>()
         })
         .collect()
 }
 
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
 enum Dir {
     Up,
     Down,
     Left,
     Right,
 }
-#[derive(Clone, Debug, Eq, Hash, PartialEq)]
-struct Search {
-    loss: u64,
-    pos: (usize, usize),
-    dir: Dir,
-    straight: u8,
-}
+impl Dir {
+    const ALL: [Dir; 4] = [Dir::Up, Dir::Down, Dir::Left, Dir::Right];
 
-fn next_steps(
-    max_i: usize,
-    max_j: usize,
-    pos: &(usize, usize),
-    dir: &Dir,
-) -> Vec<((usize, usize), Dir)> {
-    let mut steps = vec![];
-    if *dir != Dir::Down && pos.0 > 0 {
-        steps.push(((pos.0 - 1, pos.1), Dir::Up));
-    }
-    if *dir != Dir::Up && pos.0 < max_i - 1 {
-        steps.push(((pos.0 + 1, pos.1), Dir::Down));
-    }
-    if *dir != Dir::Right && pos.1 > 0 {
-        steps.push(((pos.0, pos.1 - 1), Dir::Left));
+    fn turns(&self) -> [Dir; 2] {
+        match self {
+            Dir::Up | Dir::Down => [Dir::Left, Dir::Right],
+            Dir::Left | Dir::Right => [Dir::Up, Dir::Down],
+        }
     }
-    if *dir != Dir::Left && pos.1 < max_j - 1 {
-        steps.push(((pos.0, pos.1 + 1), Dir::Right));
+
+    fn step(&self, pos: (usize, usize), max_i: usize, max_j: usize) -> Option<(usize, usize)> {
+        match self {
+            Dir::Up if pos.0 > 0 => Some((pos.0 - 1, pos.1)),
+            Dir::Down if pos.0 < max_i - 1 => Some((pos.0 + 1, pos.1)),
+            Dir::Left if pos.1 > 0 => Some((pos.0, pos.1 - 1)),
+            Dir::Right if pos.1 < max_j - 1 => Some((pos.0, pos.1 + 1)),
+            _ => None,
+        }
     }
-    steps
 }
 
-pub fn solve_one(input: &str) -> Result<String> {
-    use Dir::*;
-    let grid = &parse_input(input);
-    let max_i = grid.len();
-    let max_j = grid[0].len();
-
-    let mut lowest = vec![vec![None; max_j]; max_i];
-    lowest[0][0] = Some(0);
-    lowest[1][0] = Some(grid[1][0]);
-    lowest[0][1] = Some(grid[0][1]);
+/// Lowest loss route from the top left to the bottom right, `path` includes both ends.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Route {
+    pub loss: u64,
+    pub path: Vec<(usize, usize)>,
+}
 
-    let mut search = VecDeque::new();
-    search.push_back(Search {
-        loss: grid[1][0],
-        pos: (1, 0),
-        dir: Down,
-        straight: 0,
-    });
-    search.push_back(Search {
-        loss: grid[0][1],
-        pos: (0, 1),
-        dir: Right,
-        straight: 0,
-    });
+/// Find the lowest heat loss route for a crucible that must move at least `min_run` and at most
+/// `max_run` blocks in a straight line before turning or stopping at the end.
+pub fn min_heat_loss(grid: &[Vec<u64>], min_run: usize, max_run: usize) -> Result<Route> {
+    let max_i = grid.len();
+    let max_j = grid.first().map(|r| r.len()).unwrap_or(0);
+    if max_i == 0 || max_j == 0 || grid.iter().any(|r| r.len() != max_j) {
+        bail!("grid must be non empty and rectangular");
+    }
+    if min_run == 0 || max_run < min_run {
+        bail!("expected 0 < min_run <= max_run");
+    }
+    let end = (max_i - 1, max_j - 1);
+    if end == (0, 0) {
+        return Ok(Route {
+            loss: 0,
+            path: vec![(0, 0)],
+        });
+    }
 
-    while lowest[max_i - 1][max_j - 1].is_none() {
-        // sort by loss
-        search.make_contiguous().sort_by_key(|s| s.loss);
+    // state is position, direction moved into it, and how far moved in that direction
+    let runs = max_run + 1;
+    let index = |pos: (usize, usize), dir: Dir, run: usize| {
+        ((pos.0 * max_j + pos.1) * 4 + dir as usize) * runs + run
+    };
+    let state = |i: usize| {
+        let run = i % runs;
+        let dir = Dir::ALL[(i / runs) % 4];
+        let cell = i / runs / 4;
+        ((cell / max_j, cell % max_j), dir, run)
+    };
+
+    let mut lowest = vec![u64::MAX; max_i * max_j * 4 * runs];
+    let mut prev = vec![usize::MAX; lowest.len()];
+    let mut search = BinaryHeap::new();
+    // a run of 0 at the start lets the crucible set off in either direction
+    for dir in [Dir::Right, Dir::Down] {
+        let i = index((0, 0), dir, 0);
+        lowest[i] = 0;
+        search.push(Reverse((0, i)));
+    }
 
-        // take all elements with lowest loss
-        let mut low_loss = HashSet::new();
-        let first = search.pop_front().ok_or(eyre!("empty search vec"))?;
-        while let Some(f) = search.front() {
-            if f.loss == first.loss {
-                low_loss.insert(search.pop_front().ok_or(eyre!("empty search vec"))?);
-            } else {
-                break;
+    while let Some(Reverse((loss, i))) = search.pop() {
+        if loss > lowest[i] {
+            continue;
+        }
+        let (pos, dir, run) = state(i);
+        if pos == end && run >= min_run {
+            let mut path = vec![pos];
+            let mut p = prev[i];
+            while p != usize::MAX {
+                path.push(state(p).0);
+                p = prev[p];
             }
+            path.reverse();
+            return Ok(Route { loss, path });
         }
-        low_loss.insert(first);
 
-        // take next step of lowest loss
-        let mut low_loss = low_loss
+        let straight = (run < max_run).then_some((dir, run + 1));
+        let turns = (run == 0 || run >= min_run)
+            .then(|| dir.turns().map(|d| (d, 1)))
             .into_iter()
-            .flat_map(|s| {
-                let search = s.clone();
-                next_steps(max_i, max_j, &search.pos, &search.dir)
-                    .into_iter()
-                    .filter_map(move |n| {
-                        if n.1 == search.dir && search.straight >= 2 {
-                            None
-                        } else {
-                            Some(Search {
-                                loss: search.loss + grid[n.0 .0][n.0 .1],
-                                pos: n.0,
-                                straight: if n.1 == search.dir {
-                                    search.straight + 1
-                                } else {
-                                    0
-                                },
-                                dir: n.1,
-                            })
-                        }
-                    })
-            })
-            .collect::<Vec<Search>>();
-        // sort next steps
-        // if lowest not yet set; set it and add back into search
-        // if lowest is set already; check not falling too far behind and add back into search
-        low_loss.sort_by_key(|s| s.loss);
-        low_loss.into_iter().for_each(|s| {
-            if let Some(l) = lowest[s.pos.0][s.pos.1] {
-                // 18 is worse case additional loss when going around instead of straight
-                if s.loss <= l || s.loss - l <= 18 {
-                    search.push_back(s);
+            .flatten();
+        for (next_dir, next_run) in straight.into_iter().chain(turns) {
+            if let Some(next) = next_dir.step(pos, max_i, max_j) {
+                let next_i = index(next, next_dir, next_run);
+                let next_loss = loss + grid[next.0][next.1];
+                if next_loss < lowest[next_i] {
+                    lowest[next_i] = next_loss;
+                    prev[next_i] = i;
+                    search.push(Reverse((next_loss, next_i)));
                 }
-            } else {
-                lowest[s.pos.0][s.pos.1] = Some(s.loss);
-                search.push_back(s);
             }
-        });
+        }
     }
 
-    lowest[max_i - 1][max_j - 1]
-        .ok_or(eyre!("no finish value"))
-        .map(|v| v.to_string())
+    Err(eyre!("no route to the end"))
 }
 
-fn next_steps2(
-    max_i: usize,
-    max_j: usize,
-    pos: &(usize, usize),
-    dir: &Dir,
-    straight: u8,
-) -> Vec<((usize, usize), Dir, usize)> {
-    let mut steps = vec![];
-    fn num_steps(cur_dir: &Dir, to_dir: Dir, straight: u8) -> Option<usize> {
-        if cur_dir == &to_dir {
-            if straight >= 10 {
-                None
-            } else {
-                Some(1)
-            }
-        } else {
-            match (cur_dir, &to_dir) {
-                (Dir::Up, Dir::Down)
-                | (Dir::Down, Dir::Up)
-                | (Dir::Left, Dir::Right)
-                | (Dir::Right, Dir::Left) => None,
-                _ => Some(4),
-            }
-        }
-    }
-    if let Some(s) = num_steps(dir, Dir::Up, straight) {
-        if pos.0 >= s {
-            steps.push(((pos.0 - s, pos.1), Dir::Up, s));
-        }
-    }
-    if let Some(s) = num_steps(dir, Dir::Down, straight) {
-        if pos.0 < max_i - s {
-            steps.push(((pos.0 + s, pos.1), Dir::Down, s));
-        }
-    }
-    if let Some(s) = num_steps(dir, Dir::Left, straight) {
-        if pos.1 >= s {
-            steps.push(((pos.0, pos.1 - s), Dir::Left, s));
-        }
-    }
-    if let Some(s) = num_steps(dir, Dir::Right, straight) {
-        if pos.1 < max_j - s {
-            steps.push(((pos.0, pos.1 + s), Dir::Right, s));
-        }
-    }
-    steps
+pub fn solve_one(input: &str) -> Result<String> {
+    let grid = parse_input(input)?;
+    timing::parsed();
+    Ok(min_heat_loss(&grid, 1, 3)?.loss.to_string())
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    use Dir::*;
-    let grid = &parse_input(input);
-    let max_i = grid.len();
-    let max_j = grid[0].len();
-
-    let mut lowest = vec![vec![None; max_j]; max_i];
-    lowest[0][0] = Some(0);
-
-    let mut search = VecDeque::new();
-    search.push_back(Search {
-        loss: 0,
-        pos: (0, 0),
-        dir: Up,
-        straight: 10,
-    });
-    search.push_back(Search {
-        loss: 0,
-        pos: (0, 0),
-        dir: Left,
-        straight: 10,
-    });
-
-    while lowest[max_i - 1][max_j - 1].is_none() {
-        // sort by loss
-        search.make_contiguous().sort_by_key(|s| s.loss);
-
-        // take all elements with lowest loss
-        let mut low_loss = HashSet::new();
-        let first = search.pop_front().ok_or(eyre!("empty search vec"))?;
-        while let Some(f) = search.front() {
-            if f.loss == first.loss {
-                low_loss.insert(search.pop_front().ok_or(eyre!("empty search vec"))?);
-            } else {
-                break;
-            }
-        }
-        low_loss.insert(first);
+    let grid = parse_input(input)?;
+    timing::parsed();
+    Ok(min_heat_loss(&grid, 4, 10)?.loss.to_string())
+}
 
-        // take next step of lowest loss
-        let mut low_loss = low_loss
-            .into_iter()
-            .flat_map(|s| {
-                let search = s.clone();
-                next_steps2(max_i, max_j, &search.pos, &search.dir, search.straight)
-                    .into_iter()
-                    .map(move |n| {
-                        let add_loss = (0..n.2)
-                            .map(|i| match n.1 {
-                                Up => grid[n.0 .0 + i][n.0 .1],
-                                Down => grid[n.0 .0 - i][n.0 .1],
-                                Left => grid[n.0 .0][n.0 .1 + i],
-                                Right => grid[n.0 .0][n.0 .1 - i],
-                            })
-                            .sum::<u64>();
-                        Search {
-                            loss: search.loss + add_loss,
-                            pos: n.0,
-                            straight: if n.1 == search.dir {
-                                search.straight + n.2 as u8
-                            } else {
-                                n.2 as u8
-                            },
-                            dir: n.1,
-                        }
-                    })
-            })
-            .collect::<Vec<Search>>();
-        // sort next steps
-        // if lowest not yet set; set it and add back into search
-        // if lowest is set already; check not falling too far behind and add back into search
-        low_loss.sort_by_key(|s| s.loss);
-        low_loss.into_iter().for_each(|s| {
-            if let Some(l) = lowest[s.pos.0][s.pos.1] {
-                // 96 is worse case additional loss when going around instead of straight
-                if s.loss <= l || s.loss - l <= 96 {
-                    search.push_back(s);
-                }
-            } else {
-                lowest[s.pos.0][s.pos.1] = Some(s.loss);
-                search.push_back(s);
-            }
-        });
+/// Frames of the heat loss map with the part one route drawn block by block, then a last frame with
+/// both the part one and part two routes.
+pub fn visualize(input: &str) -> Result<Frames> {
+    const STEPS: usize = 100;
+
+    let grid = parse_input(input)?;
+    let one = min_heat_loss(&grid, 1, 3)?;
+    let two = min_heat_loss(&grid, 4, 10)?;
+
+    // heat loss 0 to 9 from dark to light, then part one route, part two route, both routes
+    let mut frames = Frames::new(
+        grid[0].len(),
+        grid.len(),
+        &[
+            "#111", "#222", "#333", "#444", "#555", "#666", "#777", "#888", "#999", "#aaa", "#f80",
+            "#3af", "#fff",
+        ],
+    );
+    let mut on_one = vec![vec![false; grid[0].len()]; grid.len()];
+    let heat = |i: usize, j: usize| grid[i][j].min(9) as u8;
+    frames.push(heat);
+    for chunk in one.path.chunks(one.path.len().div_ceil(STEPS)) {
+        chunk.iter().for_each(|&(i, j)| on_one[i][j] = true);
+        frames.push(|i, j| if on_one[i][j] { 10 } else { heat(i, j) });
     }
-
-    lowest[max_i - 1][max_j - 1]
-        .ok_or(eyre!("no finish value"))
-        .map(|v| v.to_string())
+    let mut on_two = vec![vec![false; grid[0].len()]; grid.len()];
+    two.path.iter().for_each(|&(i, j)| on_two[i][j] = true);
+    frames.push(|i, j| match (on_one[i][j], on_two[i][j]) {
+        (true, true) => 12,
+        (true, false) => 10,
+        (false, true) => 11,
+        (false, false) => heat(i, j),
+    });
+    Ok(frames)
 }
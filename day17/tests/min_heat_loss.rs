@@ -0,0 +1,79 @@
+const EXAMPLE: &str = "2413432311323
+3215453535623
+3255245654254
+3446585845452
+4546657867536
+1438598798454
+4457876987766
+3637877979653
+4654967986887
+4564679986453
+1224686865563
+2546548887735
+4322674655533";
+
+const ULTRA_EXAMPLE: &str = "111111111111
+999999999991
+999999999991
+999999999991
+999999999991";
+
+/// Check the route is contiguous from the top left to the bottom right, that its straight runs
+/// are within the limits and that the cells it enters add up to its loss.
+fn check_route(grid: &[Vec<u64>], route: &day17::Route, min_run: usize, max_run: usize) {
+    let path = &route.path;
+    assert_eq!(path.first(), Some(&(0, 0)));
+    assert_eq!(path.last(), Some(&(grid.len() - 1, grid[0].len() - 1)));
+    let steps = path
+        .windows(2)
+        .map(|w| {
+            let step = (w[1].0 as i64 - w[0].0 as i64, w[1].1 as i64 - w[0].1 as i64);
+            assert_eq!(step.0.abs() + step.1.abs(), 1, "{:?} to {:?}", w[0], w[1]);
+            step
+        })
+        .collect::<Vec<(i64, i64)>>();
+    for run in steps.chunk_by(|a, b| a == b) {
+        assert!(
+            (min_run..=max_run).contains(&run.len()),
+            "run of {}",
+            run.len()
+        );
+    }
+    assert_eq!(
+        path[1..].iter().map(|&(i, j)| grid[i][j]).sum::<u64>(),
+        route.loss
+    );
+}
+
+#[test]
+fn examples() {
+    assert_eq!(day17::solve_one(EXAMPLE).unwrap(), "102");
+    assert_eq!(day17::solve_two(EXAMPLE).unwrap(), "94");
+    assert_eq!(day17::solve_two(ULTRA_EXAMPLE).unwrap(), "71");
+}
+
+#[test]
+fn routes() {
+    for (input, min_run, max_run, loss) in [
+        (EXAMPLE, 1, 3, 102),
+        (EXAMPLE, 4, 10, 94),
+        (ULTRA_EXAMPLE, 1, 3, 59),
+        (ULTRA_EXAMPLE, 4, 10, 71),
+    ] {
+        let grid = day17::parse_input(input).unwrap();
+        let route = day17::min_heat_loss(&grid, min_run, max_run).unwrap();
+        assert_eq!(route.loss, loss);
+        check_route(&grid, &route, min_run, max_run);
+    }
+}
+
+#[test]
+fn invalid() {
+    let grid = day17::parse_input(EXAMPLE).unwrap();
+    assert!(day17::min_heat_loss(&[], 1, 3).is_err());
+    assert!(day17::min_heat_loss(&[vec![]], 1, 3).is_err());
+    assert!(day17::min_heat_loss(&[vec![1, 2], vec![3]], 1, 3).is_err());
+    assert!(day17::min_heat_loss(&grid, 0, 3).is_err());
+    assert!(day17::min_heat_loss(&grid, 4, 3).is_err());
+    assert!(day17::parse_input("12\n3x").is_err());
+}
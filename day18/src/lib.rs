@@ -1,5 +1,7 @@
 use eyre::{eyre, Result};
 use std::ops::Range;
+use utils::input::InputContext;
+use utils::timing;
 
 #[derive(Clone, Debug, PartialEq)]
 enum Dir {
@@ -9,31 +11,30 @@ enum Dir {
     Right,
 }
 
+fn parse_line(l: &str) -> Result<(Dir, i64, &str)> {
+    let (d, l) = l.split_once(' ').ok_or(eyre!("missing space"))?;
+    let d = match d {
+        "U" => Dir::Up,
+        "D" => Dir::Down,
+        "L" => Dir::Left,
+        "R" => Dir::Right,
+        _ => eyre::bail!("unknown direction {}", d),
+    };
+    let (num, col) = l.split_once(' ').ok_or(eyre!("missing second space"))?;
+    let num = num.parse::<i64>()?;
+    let col = col
+        .strip_prefix("(#")
+        .ok_or(eyre!("color missing prefix (#"))?
+        .strip_suffix(')')
+        .ok_or(eyre!("color missing suffix )"))?;
+    Ok((d, num, col))
+}
+
 fn parse_input(input: &str) -> Result<Vec<(Dir, i64, &str)>> {
     input
         .lines()
-        .map(|l| {
-            let (d, l) = l
-                .split_once(' ')
-                .ok_or(eyre!("missing space in line {}", l))?;
-            let d = match d {
-                "U" => Dir::Up,
-                "D" => Dir::Down,
-                "L" => Dir::Left,
-                "R" => Dir::Right,
-                _ => eyre::bail!("unknown direction {}", d),
-            };
-            let (num, col) = l
-                .split_once(' ')
-                .ok_or(eyre!("missing second space in line {}", l))?;
-            let num = num.parse::<i64>()?;
-            let col = col
-                .strip_prefix("(#")
-                .ok_or(eyre!("color missing prefix (#"))?
-                .strip_suffix(')')
-                .ok_or(eyre!("color missing suffix )"))?;
-            Ok((d, num, col))
-        })
+        .enumerate()
+        .map(|(i, l)| parse_line(l).line_context(i, l))
         .collect()
 }
 
@@ -97,6 +98,7 @@ fn count_fill(grid: &[Vec<char>]) -> Result<u64> {
 
 pub fn solve_one(input: &str) -> Result<String> {
     let mut plan = parse_input(input)?;
+    timing::parsed();
 
     let (min_i, max_i, min_j, max_j) = {
         let mut cur = (0, 0);
@@ -167,6 +169,7 @@ enum Edge {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let mut plan = parse_input(input)?;
+    timing::parsed();
 
     plan = plan
         .into_iter()
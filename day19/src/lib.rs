@@ -2,6 +2,8 @@ use eyre::{eyre, Result};
 use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::ops::Range;
+use utils::input::InputContext;
+use utils::{explain, timing};
 
 #[derive(Clone, Debug)]
 struct Part {
@@ -69,74 +71,81 @@ impl Rule {
     }
 }
 
+fn parse_workflow(l: &str) -> Result<(String, (Vec<Rule>, Destination))> {
+    let (name, rules) = l.split_once('{').ok_or(eyre!("missing '{{' in workflow"))?;
+    let rules = rules
+        .strip_suffix('}')
+        .ok_or(eyre!("missing '}}' workflow suffix"))?;
+    let (rules, dest) = rules
+        .rsplit_once(',')
+        .ok_or(eyre!("missing otherwise destination in rules"))?;
+    let rules = rules
+        .split(',')
+        .map(|r| {
+            let (cat, check, rest) = if let Some((cat, rest)) = r.split_once('>') {
+                (cat, Ordering::Greater, rest)
+            } else {
+                let (cat, rest) = r
+                    .split_once('<')
+                    .ok_or(eyre!("expected '>' or '<' in rule"))?;
+                (cat, Ordering::Less, rest)
+            };
+            let (val, dest) = rest.split_once(':').ok_or(eyre!("missing ':' in rule"))?;
+            Ok(Rule {
+                category: Category::new(cat)?,
+                val: val.parse::<u64>()?,
+                check,
+                destination: Destination::new(dest),
+            })
+        })
+        .collect::<Result<Vec<Rule>>>()?;
+    Ok((name.to_string(), (rules, Destination::new(dest))))
+}
+
 fn parse_workflows(input: &str) -> Result<HashMap<String, (Vec<Rule>, Destination)>> {
     input
         .lines()
-        .map(|l| {
-            let (name, rules) = l.split_once('{').ok_or(eyre!("missing '{{' in workflow"))?;
-            let rules = rules
-                .strip_suffix('}')
-                .ok_or(eyre!("missing '}}' workflow suffix"))?;
-            let (rules, dest) = rules
-                .rsplit_once(',')
-                .ok_or(eyre!("missing otherwise destination in rules"))?;
-            let rules = rules
-                .split(',')
-                .map(|r| {
-                    let (cat, check, rest) = if let Some((cat, rest)) = r.split_once('>') {
-                        (cat, Ordering::Greater, rest)
-                    } else {
-                        let (cat, rest) = r
-                            .split_once('<')
-                            .ok_or(eyre!("expected '>' or '<' in rule"))?;
-                        (cat, Ordering::Less, rest)
-                    };
-                    let (val, dest) = rest.split_once(':').ok_or(eyre!("missing ':' in rule"))?;
-                    Ok(Rule {
-                        category: Category::new(cat)?,
-                        val: val.parse::<u64>()?,
-                        check,
-                        destination: Destination::new(dest),
-                    })
-                })
-                .collect::<Result<Vec<Rule>>>()?;
-            Ok((name.to_string(), (rules, Destination::new(dest))))
-        })
+        .enumerate()
+        .map(|(i, l)| parse_workflow(l).line_context(i, l))
         .collect()
 }
 
-fn parse_parts(input: &str) -> Result<Vec<Part>> {
+fn parse_part(l: &str) -> Result<Part> {
+    let l = l
+        .strip_prefix('{')
+        .ok_or(eyre!("missing '{{' at start of part"))?
+        .strip_suffix('}')
+        .ok_or(eyre!("missing '}}' at end of part"))?;
+    let cats = l.split(',').collect::<Vec<&str>>();
+    if cats.len() != 4 {
+        eyre::bail!("expecting 4 categories in part");
+    }
+    Ok(Part {
+        x: cats[0]
+            .strip_prefix("x=")
+            .ok_or(eyre!("first category should be x"))?
+            .parse::<u64>()?,
+        m: cats[1]
+            .strip_prefix("m=")
+            .ok_or(eyre!("second category should be m"))?
+            .parse::<u64>()?,
+        a: cats[2]
+            .strip_prefix("a=")
+            .ok_or(eyre!("third category should be a"))?
+            .parse::<u64>()?,
+        s: cats[3]
+            .strip_prefix("s=")
+            .ok_or(eyre!("fourth category should be s"))?
+            .parse::<u64>()?,
+    })
+}
+
+/// Parse the parts, `first_line` is the index of the first part's line in the input.
+fn parse_parts(input: &str, first_line: usize) -> Result<Vec<Part>> {
     input
         .lines()
-        .map(|l| {
-            let l = l
-                .strip_prefix('{')
-                .ok_or(eyre!("missing '{{' at start of part"))?
-                .strip_suffix('}')
-                .ok_or(eyre!("missing '}}' at end of part"))?;
-            let cats = l.split(',').collect::<Vec<&str>>();
-            if cats.len() != 4 {
-                eyre::bail!("expecting 4 categories in part");
-            }
-            Ok(Part {
-                x: cats[0]
-                    .strip_prefix("x=")
-                    .ok_or(eyre!("first category should be x"))?
-                    .parse::<u64>()?,
-                m: cats[1]
-                    .strip_prefix("m=")
-                    .ok_or(eyre!("second category should be m"))?
-                    .parse::<u64>()?,
-                a: cats[2]
-                    .strip_prefix("a=")
-                    .ok_or(eyre!("third category should be a"))?
-                    .parse::<u64>()?,
-                s: cats[3]
-                    .strip_prefix("s=")
-                    .ok_or(eyre!("fourth category should be s"))?
-                    .parse::<u64>()?,
-            })
-        })
+        .enumerate()
+        .map(|(i, l)| parse_part(l).line_context(first_line + i, l))
         .collect()
 }
 
@@ -144,13 +153,17 @@ pub fn solve_one(input: &str) -> Result<String> {
     let (workflows, parts) = input
         .split_once("\n\n")
         .ok_or(eyre!("missing workflows parts split"))?;
+    let first_part = workflows.lines().count() + 1;
     let workflows = parse_workflows(workflows)?;
-    let parts = parse_parts(parts)?;
+    let parts = parse_parts(parts, first_part)?;
+    timing::parsed();
     let start = Destination::new("in");
+    explain::section("workflow path of each part");
     Ok(parts
         .iter()
         .map(|p| {
             let mut d = start.clone();
+            let mut path = vec![];
             while let Destination::Workflow(wf) = d {
                 let (rules, dest) = workflows
                     .get(&wf)
@@ -159,7 +172,20 @@ pub fn solve_one(input: &str) -> Result<String> {
                     .iter()
                     .find_map(|r| r.check(p))
                     .unwrap_or(dest.clone());
+                path.push(wf);
             }
+            explain::line(|| {
+                let end = if d == Destination::Accept { "A" } else { "R" };
+                format!(
+                    "{{x={},m={},a={},s={}}}: {} -> {}",
+                    p.x,
+                    p.m,
+                    p.a,
+                    p.s,
+                    path.join(" -> "),
+                    end
+                )
+            });
             Ok((p, d))
         })
         .collect::<Result<Vec<(&Part, Destination)>>>()?
@@ -249,6 +275,7 @@ pub fn solve_two(input: &str) -> Result<String> {
         .split_once("\n\n")
         .ok_or(eyre!("missing workflows parts split"))?;
     let workflows = parse_workflows(workflows)?;
+    timing::parsed();
     let mut accepted_parts = vec![];
     let mut stack = {
         let in_wf = workflows.get("in").ok_or(eyre!("no in workflow found"))?;
@@ -314,13 +341,21 @@ pub fn solve_two(input: &str) -> Result<String> {
         }
     }
 
+    explain::section("accepted ratings");
     Ok(accepted_parts
         .into_iter()
         .map(|p| {
-            (p.x.end - p.x.start)
+            let combinations = (p.x.end - p.x.start)
                 * (p.m.end - p.m.start)
                 * (p.a.end - p.a.start)
-                * (p.s.end - p.s.start)
+                * (p.s.end - p.s.start);
+            explain::line(|| {
+                format!(
+                    "x {:?}, m {:?}, a {:?}, s {:?}: {} combinations",
+                    p.x, p.m, p.a, p.s, combinations
+                )
+            });
+            combinations
         })
         .sum::<u64>()
         .to_string())
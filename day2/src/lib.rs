@@ -1,63 +1,142 @@
-use eyre::{bail, eyre, Result};
+use eyre::{eyre, Result};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+use std::str::FromStr;
+use utils::input::InputContext;
+use utils::timing;
 
-#[derive(Clone, Debug, Default)]
-struct Rgb {
-    r: i64,
-    g: i64,
-    b: i64,
+/// Bag part one checks the games against.
+pub const DEFAULT_BAG: &str = "red=12,green=13,blue=14";
+
+/// Number of cubes of each colour, colours not in the map have no cubes.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Cubes(pub BTreeMap<String, u64>);
+impl Cubes {
+    pub fn count(&self, colour: &str) -> u64 {
+        self.0.get(colour).copied().unwrap_or_default()
+    }
+
+    /// Whether every count is within the counts of `bag`.
+    pub fn fits_in(&self, bag: &Cubes) -> bool {
+        self.0
+            .iter()
+            .all(|(colour, &count)| count <= bag.count(colour))
+    }
+}
+impl FromStr for Cubes {
+    type Err = eyre::Report;
+
+    /// Parse `colour=count` pairs separated by commas, e.g. `red=12,green=13,blue=14`.
+    fn from_str(s: &str) -> Result<Self> {
+        s.split(',')
+            .filter(|p| !p.trim().is_empty())
+            .map(|p| {
+                let (colour, count) = p.split_once('=').ok_or(eyre!("missing '=' in '{}'", p))?;
+                Ok((colour.trim().to_string(), count.trim().parse::<u64>()?))
+            })
+            .collect::<Result<BTreeMap<String, u64>>>()
+            .map(Self)
+    }
+}
+impl fmt::Display for Cubes {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let pairs = self
+            .0
+            .iter()
+            .map(|(colour, count)| format!("{}={}", colour, count))
+            .collect::<Vec<String>>();
+        write!(f, "{}", pairs.join(","))
+    }
+}
+
+/// Game of cubes drawn from a bag, each round shows some of the bag's cubes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Game {
+    pub id: u64,
+    pub rounds: Vec<Cubes>,
+}
+impl Game {
+    /// Whether the game could have been played with `bag`.
+    pub fn possible_with(&self, bag: &Cubes) -> bool {
+        self.rounds.iter().all(|round| round.fits_in(bag))
+    }
+
+    /// Fewest cubes of each colour the bag must have held for the game.
+    pub fn min_bag(&self) -> Cubes {
+        let mut min = BTreeMap::new();
+        for round in &self.rounds {
+            for (colour, &count) in &round.0 {
+                let c = min.entry(colour.clone()).or_insert(0);
+                *c = count.max(*c);
+            }
+        }
+        Cubes(min)
+    }
+}
+
+fn parse_game(line: &str) -> Result<Game> {
+    let (id, l) = line.split_once(": ").ok_or(eyre!("missing ': '"))?;
+    let id = id
+        .strip_prefix("Game ")
+        .ok_or(eyre!("missing 'Game ' prefix"))?
+        .parse::<u64>()?;
+    let rounds = l
+        .split("; ")
+        .map(|r| {
+            let mut cubes = BTreeMap::new();
+            for p in r.split(", ") {
+                let (num, colour) = p.split_once(' ').ok_or(eyre!("missing ' '"))?;
+                *cubes.entry(colour.to_string()).or_insert(0) += num.parse::<u64>()?;
+            }
+            Ok(Cubes(cubes))
+        })
+        .collect::<Result<Vec<Cubes>>>()?;
+    Ok(Game { id, rounds })
 }
 
-fn parse_input(input: &str) -> Result<Vec<Vec<Rgb>>> {
+pub fn parse_input(input: &str) -> Result<Vec<Game>> {
     input
         .lines()
-        .map(|l| {
-            let (_, l) = l.split_once(": ").ok_or(eyre!("missing ': '"))?;
-            l.split("; ")
-                .map(|r| {
-                    let mut rgb = Rgb::default();
-                    for p in r.split(", ") {
-                        let (num, colour) = p.split_once(' ').ok_or(eyre!("missing ' '"))?;
-                        let num = num.parse::<i64>()?;
-                        match colour {
-                            "red" => rgb.r = num,
-                            "green" => rgb.g = num,
-                            "blue" => rgb.b = num,
-                            _ => bail!("unknown colour {colour}"),
-                        }
-                    }
-                    Ok(rgb)
-                })
-                .collect::<Result<Vec<Rgb>>>()
-        })
-        .collect::<Result<Vec<Vec<Rgb>>>>()
+        .enumerate()
+        .map(|(i, l)| parse_game(l).line_context(i, l))
+        .collect::<Result<Vec<Game>>>()
+}
+
+/// Every colour seen in the games.
+pub fn colours(games: &[Game]) -> BTreeSet<String> {
+    games
+        .iter()
+        .flat_map(|g| g.rounds.iter().flat_map(|r| r.0.keys().cloned()))
+        .collect()
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
-    Ok(parse_input(input)?
+    solve_one_with(input, &DEFAULT_BAG.parse()?)
+}
+
+/// Part one with the games checked against `bag` rather than the puzzle's bag.
+pub fn solve_one_with(input: &str, bag: &Cubes) -> Result<String> {
+    let games = parse_input(input)?;
+    timing::parsed();
+    Ok(games
         .iter()
-        .enumerate()
-        .filter(|(_, game)| {
-            !game
-                .iter()
-                .any(|rgb| rgb.r > 12 || rgb.g > 13 || rgb.b > 14)
-        })
-        .map(|(i, _)| i + 1)
-        .sum::<usize>()
+        .filter(|game| game.possible_with(bag))
+        .map(|game| game.id)
+        .sum::<u64>()
         .to_string())
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    Ok(parse_input(input)?
+    let games = parse_input(input)?;
+    timing::parsed();
+    // a colour missing from a game's rounds has none in its minimum bag, so its power is 0
+    let colours = colours(&games);
+    Ok(games
         .iter()
         .map(|game| {
-            let mut min = Rgb::default();
-            for round in game {
-                min.r = min.r.max(round.r);
-                min.g = min.g.max(round.g);
-                min.b = min.b.max(round.b);
-            }
-            min.r * min.g * min.b
+            let min = game.min_bag();
+            colours.iter().map(|c| min.count(c)).product::<u64>()
         })
-        .sum::<i64>()
+        .sum::<u64>()
         .to_string())
 }
@@ -1,8 +1,15 @@
 use utils::derive::aoc;
 
-#[aoc(day2, part1)]
-fn solve_one(input: &str) -> eyre::Result<String> {
-    day2::solve_one(input)
+#[derive(Debug, clap::Args)]
+struct BagArgs {
+    /// bag to check the games against in part one, as colour=count pairs
+    #[arg(long, default_value = day2::DEFAULT_BAG)]
+    bag: day2::Cubes,
+}
+
+#[aoc(day2, part1, args = BagArgs)]
+fn solve_one(input: &str, args: &BagArgs) -> eyre::Result<String> {
+    day2::solve_one_with(input, &args.bag)
 }
 
 #[aoc(day2, part2)]
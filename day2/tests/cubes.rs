@@ -0,0 +1,58 @@
+use day2::{Cubes, Game};
+use std::collections::BTreeMap;
+
+fn cubes(pairs: &[(&str, u64)]) -> Cubes {
+    Cubes(
+        pairs
+            .iter()
+            .map(|&(c, n)| (c.to_string(), n))
+            .collect::<BTreeMap<String, u64>>(),
+    )
+}
+
+#[test]
+fn parse_cubes() {
+    let bag = day2::DEFAULT_BAG.parse::<Cubes>().unwrap();
+    assert_eq!(bag, cubes(&[("red", 12), ("green", 13), ("blue", 14)]));
+    assert_eq!(bag.to_string(), "blue=14,green=13,red=12");
+    assert_eq!(
+        " purple = 3, ,red=1,".parse::<Cubes>().unwrap(),
+        cubes(&[("purple", 3), ("red", 1)])
+    );
+    assert_eq!("".parse::<Cubes>().unwrap(), Cubes::default());
+    assert!("red".parse::<Cubes>().is_err());
+    assert!("red=x".parse::<Cubes>().is_err());
+    assert!("red=-1".parse::<Cubes>().is_err());
+}
+
+#[test]
+fn games() {
+    let games = day2::parse_input(
+        "Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green
+Game 3: 8 green, 6 blue, 20 red; 5 blue, 4 red, 13 green; 5 green, 1 red
+Game 7: 2 purple; 1 red, 1 purple",
+    )
+    .unwrap();
+    let bag = cubes(&[("red", 12), ("green", 13), ("blue", 14)]);
+    assert_eq!(
+        games
+            .iter()
+            .map(|g| g.possible_with(&bag))
+            .collect::<Vec<bool>>(),
+        [true, false, false]
+    );
+    assert!(games[2].possible_with(&cubes(&[("purple", 2), ("red", 1)])));
+    assert!(!games[2].possible_with(&cubes(&[("purple", 1), ("red", 1)])));
+
+    assert_eq!(
+        games[0].min_bag(),
+        cubes(&[("red", 4), ("green", 2), ("blue", 6)])
+    );
+    assert_eq!(games[2].min_bag(), cubes(&[("purple", 2), ("red", 1)]));
+    let empty = Game {
+        id: 9,
+        rounds: vec![],
+    };
+    assert_eq!(empty.min_bag(), Cubes::default());
+    assert!(empty.possible_with(&Cubes::default()));
+}
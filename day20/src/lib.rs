@@ -1,5 +1,7 @@
 use eyre::{eyre, Result};
 use std::collections::{HashMap, VecDeque};
+use utils::input::InputContext;
+use utils::{log, timing};
 
 #[derive(Clone, Debug, PartialEq)]
 enum Pulse {
@@ -47,24 +49,25 @@ impl Module {
     }
 }
 
+fn parse_module(l: &str) -> Result<(&str, (char, Vec<&str>))> {
+    let (n, to) = l.split_once(" -> ").ok_or(eyre!("no \" -> \""))?;
+    let (n, m) = if let Some(n) = n.strip_prefix('%') {
+        (n, '%')
+    } else if let Some(n) = n.strip_prefix('&') {
+        (n, '&')
+    } else if n == "broadcaster" {
+        (n, 'b')
+    } else {
+        eyre::bail!("unknown module type {}", n);
+    };
+    Ok((n, (m, to.split(", ").collect())))
+}
+
 fn parse_input(input: &str) -> Result<HashMap<String, Module>> {
     let modules = input
         .lines()
-        .map(|l| {
-            let (n, to) = l
-                .split_once(" -> ")
-                .ok_or(eyre!("no \" -> \" in line {}", l))?;
-            let (n, m) = if let Some(n) = n.strip_prefix('%') {
-                (n, '%')
-            } else if let Some(n) = n.strip_prefix('&') {
-                (n, '&')
-            } else if n == "broadcaster" {
-                (n, 'b')
-            } else {
-                eyre::bail!("unknown module type {}", n);
-            };
-            Ok((n, (m, to.split(", ").collect())))
-        })
+        .enumerate()
+        .map(|(i, l)| parse_module(l).line_context(i, l))
         .collect::<Result<HashMap<&str, (char, Vec<&str>)>>>()?;
 
     let mut m = HashMap::new();
@@ -109,6 +112,7 @@ fn parse_input(input: &str) -> Result<HashMap<String, Module>> {
 
 pub fn solve_one(input: &str) -> Result<String> {
     let mut modules = parse_input(input)?;
+    timing::parsed();
     let mut high = 0;
     let mut low = 0;
     for _ in 0..1000 {
@@ -136,6 +140,7 @@ pub fn solve_one(input: &str) -> Result<String> {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let mut modules = parse_input(input)?;
+    timing::parsed();
     let to_rx = modules.iter().find_map(|(n, m)| match m {
         Module::Conjuction { output, .. } => {
             if output.contains(&"rx".to_string()) {
@@ -167,7 +172,7 @@ pub fn solve_two(input: &str) -> Result<String> {
                 _ => None,
             })
             .collect::<HashMap<String, Option<u64>>>();
-        println!("{:?}", prevs);
+        log::debug(|| format!("modules sending to {}: {:?}", to_rx, prevs));
         let mut button_count = 0;
         while prevs.values().any(|v| v.is_none()) {
             let mut pulses = VecDeque::new();
@@ -1,5 +1,10 @@
-use eyre::{eyre, Result};
-use std::collections::HashSet;
+use eyre::{bail, eyre, Result};
+use num_integer::Integer;
+use std::collections::{HashSet, VecDeque};
+use utils::input::InputContext;
+use utils::progress;
+use utils::timing;
+use utils::visualize::Frames;
 
 #[allow(clippy::type_complexity)]
 fn parse_input(input: &str) -> Result<((usize, usize), Vec<Vec<bool>>)> {
@@ -10,23 +15,36 @@ fn parse_input(input: &str) -> Result<((usize, usize), Vec<Vec<bool>>)> {
         .map(|(i, l)| {
             l.chars()
                 .enumerate()
-                .map(|(j, c)| match c {
-                    '.' => Ok(true),
-                    '#' => Ok(false),
-                    'S' => {
-                        if s.is_none() {
-                            s = Some((i, j));
-                            Ok(true)
-                        } else {
-                            Err(eyre!("only one 'S' expected"))
+                .map(|(j, c)| {
+                    match c {
+                        '.' => Ok(true),
+                        '#' => Ok(false),
+                        'S' => {
+                            if s.is_none() {
+                                s = Some((i, j));
+                                Ok(true)
+                            } else {
+                                Err(eyre!("only one 'S' expected"))
+                            }
                         }
+                        _ => Err(eyre!("unknown char {}", c)),
                     }
-                    _ => Err(eyre!("unknown char {}", c)),
+                    .column_context(i, j, l)
                 })
                 .collect::<Result<Vec<bool>>>()
         })
-        .collect::<Result<Vec<Vec<bool>>>>();
-    Ok((s.ok_or(eyre!("no 'S' found"))?, gs?))
+        .collect::<Result<Vec<Vec<bool>>>>()?;
+    let s = s.ok_or(eyre!("no 'S' found"))?;
+    // every row must be as long as the first, so none are empty as one of them holds the S
+    let width = gs[0].len();
+    if let Some((i, l)) = input
+        .lines()
+        .enumerate()
+        .find(|(_, l)| l.chars().count() != width)
+    {
+        return Err(eyre!("expected {} chars, the first line's length", width)).line_context(i, l);
+    }
+    Ok((s, gs))
 }
 
 fn next_steps(gardens: &[Vec<bool>], step: (usize, usize)) -> Vec<(usize, usize)> {
@@ -48,6 +66,7 @@ fn next_steps(gardens: &[Vec<bool>], step: (usize, usize)) -> Vec<(usize, usize)
 
 pub fn solve_one(input: &str) -> Result<String> {
     let (s, gardens) = parse_input(input)?;
+    timing::parsed();
     let mut odd = HashSet::new();
     let mut new_odd = HashSet::new();
     let mut even = HashSet::new();
@@ -69,133 +88,159 @@ pub fn solve_one(input: &str) -> Result<String> {
     Ok(even.len().to_string())
 }
 
-fn next_steps_repeating(
-    gardens: &[Vec<bool>],
-    max_i: i64,
-    max_j: i64,
-    step: (i64, i64),
-) -> Vec<(i64, i64)> {
-    let mut next = Vec::with_capacity(4);
-    if gardens[(step.0 - 1).rem_euclid(max_i) as usize][step.1.rem_euclid(max_j) as usize] {
-        next.push((step.0 - 1, step.1));
+/// Number of garden plots at each distance from the start, on the infinitely
+/// repeating map, out to some step horizon.
+struct Reachable {
+    // plots reachable in exactly `n` steps, i.e. at distance `<= n` with same parity
+    counts: Vec<u64>,
+}
+impl Reachable {
+    fn bfs(gardens: &[Vec<bool>], s: (usize, usize), horizon: usize) -> Self {
+        let (max_i, max_j) = (gardens.len() as i64, gardens[0].len() as i64);
+        let side = 2 * horizon + 1;
+        let mut dist = vec![u32::MAX; side * side];
+        let mut queue = VecDeque::new();
+        dist[horizon * side + horizon] = 0;
+        queue.push_back((horizon, horizon));
+
+        let mut hist = vec![0_u64; horizon + 1];
+        let mut step = 0;
+        while let Some((i, j)) = queue.pop_front() {
+            let d = dist[i * side + j];
+            hist[d as usize] += 1;
+            if d > step {
+                step = d;
+                progress::report(step.into(), Some(horizon as u64));
+            }
+            if d as usize == horizon {
+                continue;
+            }
+            for (ni, nj) in [
+                (i.wrapping_sub(1), j),
+                (i + 1, j),
+                (i, j.wrapping_sub(1)),
+                (i, j + 1),
+            ] {
+                if ni >= side || nj >= side || dist[ni * side + nj] != u32::MAX {
+                    continue;
+                }
+                let gi = (s.0 as i64 + ni as i64 - horizon as i64).rem_euclid(max_i);
+                let gj = (s.1 as i64 + nj as i64 - horizon as i64).rem_euclid(max_j);
+                if gardens[gi as usize][gj as usize] {
+                    dist[ni * side + nj] = d + 1;
+                    queue.push_back((ni, nj));
+                }
+            }
+        }
+
+        let mut counts = hist;
+        for n in 2..counts.len() {
+            counts[n] += counts[n - 2];
+        }
+        Self { counts }
     }
-    if gardens[(step.0 + 1).rem_euclid(max_i) as usize][step.1.rem_euclid(max_j) as usize] {
-        next.push((step.0 + 1, step.1));
+
+    fn count(&self, steps: usize) -> u64 {
+        self.counts[steps]
     }
-    if gardens[step.0.rem_euclid(max_i) as usize][(step.1 - 1).rem_euclid(max_j) as usize] {
-        next.push((step.0, step.1 - 1));
+}
+
+/// Give up looking for quadratic growth once the distances out to the sampled horizon would need
+/// more than this many cells, 64MiB of distances.
+const MAX_CELLS: usize = 1 << 24;
+
+/// Number of garden plots reachable in exactly `steps` steps on the infinitely repeating map.
+pub fn solve_steps(input: &str, steps: usize) -> Result<u64> {
+    let (s, gardens) = parse_input(input)?;
+    timing::parsed();
+    let max_j = gardens[0].len();
+
+    // the map repeats every lcm of width and height, doubled if odd so that
+    // the step parity is also the same each period
+    let mut period = gardens.len().lcm(&max_j);
+    if period % 2 == 1 {
+        period *= 2;
     }
-    if gardens[step.0.rem_euclid(max_i) as usize][(step.1 + 1).rem_euclid(max_j) as usize] {
-        next.push((step.0, step.1 + 1));
+    let rem = steps % period;
+    let target = (steps / period) as i128;
+
+    // Once the steps have spread over enough repeats of the map, the reachable count grows
+    // quadratically for every period walked. Sample the counts each period until the second
+    // difference settles, then extrapolate out to the requested step count. If the steps are
+    // within the sampled horizon just count them directly.
+    let mut periods = 4;
+    loop {
+        let horizon = (rem + periods * period).min(steps);
+        let side = 2 * horizon + 1;
+        if side.saturating_mul(side) > MAX_CELLS {
+            bail!(
+                "reachable plots did not settle into quadratic growth before a {} step horizon, \
+                 which is too large to sample",
+                horizon
+            );
+        }
+        if steps == horizon {
+            return Ok(Reachable::bfs(&gardens, s, steps).count(steps));
+        }
+
+        let reachable = Reachable::bfs(&gardens, s, horizon);
+        let samples = (0..=periods)
+            .map(|k| reachable.count(rem + k * period) as i128)
+            .collect::<Vec<i128>>();
+        let second_diffs = samples
+            .windows(3)
+            .map(|w| w[2] - 2 * w[1] + w[0])
+            .collect::<Vec<i128>>();
+        let settled = &second_diffs[second_diffs.len() - 3..];
+        if settled.iter().all(|&d| d == settled[0]) {
+            let last = samples[periods];
+            let diff = last - samples[periods - 1];
+            let m = target - periods as i128;
+            return Ok((last + m * diff + settled[0] * m * (m + 1) / 2) as u64);
+        }
+
+        periods *= 2;
     }
-    next
 }
 
-// The steps should spread like a diamond, with repeating blocks inside, and repeating diagonals
-// around the edge. Compute minimum size diamond so that we have examples of all required blocks,
-// diagonals and corners and then can use those to scale up to full size.
-// Gotta be careful around odd/even blocks and diagonals from the center.
 pub fn solve_two(input: &str) -> Result<String> {
-    let (s, gardens) = parse_input(input)?;
-    let start = (s.0 as i64, s.1 as i64);
-    let total_steps = 26_501_365;
-    let max_i = gardens.len() as i64;
-    let max_j = gardens[0].len() as i64;
-    if max_i != max_j {
-        eyre::bail!("only works if input width equals input height");
-    }
+    Ok(solve_steps(input, 26_501_365)?.to_string())
+}
 
-    let div = total_steps / (max_i);
-    let rem = total_steps % (max_i);
+/// Frames of the garden plots reachable in exactly each number of steps up to 64, on the single
+/// map as in part one.
+pub fn visualize(input: &str) -> Result<Frames> {
+    const STEPS: u32 = 64;
 
-    // cover enough area so that we have:
-    // - full even block
-    // - full odd block
-    // - NW/NE/SW/SE diagonal block
-    // - all 4 corners
-    let min_blocks = 2 + (div % 2);
-    let min_steps = (min_blocks * max_i) + rem;
-    let mut odd = HashSet::new();
-    let mut new_odd = HashSet::new();
-    let mut even = HashSet::new();
-    even.insert(start);
-    let mut new_even = HashSet::new();
-    new_even.insert(start);
-    for i in 1..=min_steps {
-        let (steps, last_steps, new_steps) = if i % 2 == 0 {
-            (&mut even, &new_odd, &mut new_even)
-        } else {
-            (&mut odd, &new_even, &mut new_odd)
-        };
-        *new_steps = last_steps
-            .iter()
-            .flat_map(|s| next_steps_repeating(&gardens, max_i, max_j, *s))
-            .filter(|s| steps.insert(*s))
-            .collect();
+    let (s, gardens) = parse_input(input)?;
+    let width = gardens[0].len();
+    let mut dist = vec![vec![u32::MAX; width]; gardens.len()];
+    dist[s.0][s.1] = 0;
+    let mut queue = VecDeque::from([s]);
+    while let Some(pos) = queue.pop_front() {
+        let d = dist[pos.0][pos.1];
+        if d == STEPS {
+            continue;
+        }
+        for next in next_steps(&gardens, pos) {
+            if dist[next.0][next.1] == u32::MAX {
+                dist[next.0][next.1] = d + 1;
+                queue.push_back(next);
+            }
+        }
     }
 
-    // finished on odd step count, use odd steps
-    let even_block = odd
-        .iter()
-        .filter(|(i, j)| (0..max_i).contains(i) && (0..max_j).contains(j))
-        .count();
-    let odd_block = odd
-        .iter()
-        .filter(|(i, j)| (0..max_i).contains(i) && (max_j..max_j * 2).contains(j))
-        .count();
-    let nw_diag = odd
-        .iter()
-        .filter(|(i, j)| (-max_i..0).contains(i) && j < &(-max_j * (min_blocks - 2)))
-        .count();
-    let ne_diag = odd
-        .iter()
-        .filter(|(i, j)| (-max_i..0).contains(i) && j >= &(max_j * (min_blocks - 1)))
-        .count();
-    let sw_diag = odd
-        .iter()
-        .filter(|(i, j)| (max_i..max_i * 2).contains(i) && j < &(-max_j * (min_blocks - 2)))
-        .count();
-    let se_diag = odd
-        .iter()
-        .filter(|(i, j)| (max_i..max_i * 2).contains(i) && j >= &(max_j * (min_blocks - 1)))
-        .count();
-    let n_corner = odd
-        .iter()
-        .filter(|(i, _)| i < &(-max_i * (min_blocks - 1)))
-        .count();
-    let s_corner = odd
-        .iter()
-        .filter(|(i, _)| i >= &(max_i * min_blocks))
-        .count();
-    let w_corner = odd
-        .iter()
-        .filter(|(i, j)| (0..max_i).contains(i) && j < &(-max_j * (min_blocks - 1)))
-        .count();
-    let e_corner = odd
-        .iter()
-        .filter(|(i, j)| (0..max_i).contains(i) && j >= &(max_j * min_blocks))
-        .count();
-
-    // scale up add add together blocks, diags and corners
-    let (more, less) = if div % 2 == 0 {
-        (odd_block, even_block)
-    } else {
-        (even_block, odd_block)
-    };
-    let sum = (0..=div as usize)
-        .map(|r| {
-            (if r == 0 {
-                n_corner + s_corner
+    // rock, garden, reachable
+    let mut frames = Frames::new(width, gardens.len(), &["#555", "#222", "#3a3"]);
+    for step in 0..=STEPS {
+        frames.push(|i, j| {
+            let d = dist[i][j];
+            if d <= step && d % 2 == step % 2 {
+                2
             } else {
-                let row_blocks = (r * more) + ((r - 1) * less);
-                if r == div as usize {
-                    w_corner + e_corner + row_blocks
-                } else {
-                    nw_diag + ne_diag + sw_diag + se_diag + (2 * row_blocks)
-                }
-            }) as u64
-        })
-        .sum::<u64>();
-
-    Ok(sum.to_string())
+                u8::from(gardens[i][j])
+            }
+        });
+    }
+    Ok(frames)
 }
@@ -0,0 +1,38 @@
+const EXAMPLE: &str = "...........
+.....###.#.
+.###.##..#.
+..#.#...#..
+....#.#....
+.##..S####.
+.##..#...#.
+.......##..
+.##.#.####.
+.##..##.##.
+...........";
+
+#[test]
+fn example_steps() {
+    for (steps, plots) in [
+        (6, 16),
+        (10, 50),
+        (50, 1594),
+        (100, 6536),
+        (500, 167004),
+        (1000, 668697),
+        (5000, 16733044),
+    ] {
+        assert_eq!(
+            day21::solve_steps(EXAMPLE, steps).unwrap(),
+            plots,
+            "{steps} steps"
+        );
+    }
+}
+
+#[test]
+fn wide_map_within_budget() {
+    // an open map is every plot with the same parity within the steps
+    let input = format!("S{}", ".".repeat(1024));
+    assert_eq!(day21::solve_steps(&input, 10).unwrap(), 121);
+    assert!(day21::solve_steps(&input, 1_000_000).is_err());
+}
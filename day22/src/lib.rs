@@ -1,25 +1,31 @@
 use eyre::{eyre, Result};
 use std::collections::{HashMap, HashSet};
+use utils::input::InputContext;
+use utils::{explain, log, timing};
+
+#[allow(clippy::type_complexity)]
+fn parse_brick(l: &str) -> Result<((usize, usize, usize), (usize, usize, usize))> {
+    let (s, e) = l.split_once('~').ok_or(eyre!("missing '~'"))?;
+    let s = s
+        .split(',')
+        .map(|v| Ok(v.parse::<usize>()?))
+        .collect::<Result<Vec<usize>>>()?;
+    let e = e
+        .split(',')
+        .map(|v| Ok(v.parse::<usize>()?))
+        .collect::<Result<Vec<usize>>>()?;
+    if s.len() != 3 || e.len() != 3 {
+        Err(eyre!("expected 3 numbers per coordinate"))
+    } else {
+        Ok(((s[0], s[1], s[2]), (e[0], e[1], e[2])))
+    }
+}
 
 fn parse_input(input: &str) -> Result<Vec<Vec<Vec<Option<u16>>>>> {
     let bricks = input
         .lines()
-        .map(|l| {
-            let (s, e) = l.split_once('~').ok_or(eyre!("missing '~' {}", l))?;
-            let s = s
-                .split(',')
-                .map(|v| Ok(v.parse::<usize>()?))
-                .collect::<Result<Vec<usize>>>()?;
-            let e = e
-                .split(',')
-                .map(|v| Ok(v.parse::<usize>()?))
-                .collect::<Result<Vec<usize>>>()?;
-            if s.len() != 3 || e.len() != 3 {
-                Err(eyre!("expected 3 numbers per coordinate {}", l))
-            } else {
-                Ok(((s[0], s[1], s[2]), (e[0], e[1], e[2])))
-            }
-        })
+        .enumerate()
+        .map(|(i, l)| parse_brick(l).line_context(i, l))
         .collect::<Result<Vec<((usize, usize, usize), (usize, usize, usize))>>>()?;
     let mut max = (0, 0, 0);
     bricks.iter().for_each(|(bs, be)| {
@@ -151,17 +157,39 @@ fn get_depends_supports_map(
         prev_blocks = blocks;
     }
 
+    if explain::explaining() {
+        explain::section("brick support graph");
+        let mut bricks = supports.keys().copied().collect::<Vec<u16>>();
+        bricks.sort();
+        let sorted = |set: Option<&HashSet<u16>>| {
+            let mut v = set.into_iter().flatten().copied().collect::<Vec<u16>>();
+            v.sort();
+            v
+        };
+        for b in bricks {
+            explain::line(|| {
+                format!(
+                    "brick {}: rests on {:?}, supports {:?}",
+                    b,
+                    sorted(depends_on.get(&b)),
+                    sorted(supports.get(&b))
+                )
+            });
+        }
+    }
+
     (depends_on, supports)
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
     let mut grid = parse_input(input)?;
+    timing::parsed();
 
     move_blocks_down(&mut grid)?;
 
     let (depends_on, supports) = get_depends_supports_map(&grid);
 
-    dbg!(supports.len());
+    log::debug(|| format!("{} bricks after falling", supports.len()));
     Ok(supports
         .values()
         .map(|supps| {
@@ -187,6 +215,7 @@ pub fn solve_one(input: &str) -> Result<String> {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let mut grid = parse_input(input)?;
+    timing::parsed();
 
     move_blocks_down(&mut grid)?;
 
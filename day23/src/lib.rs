@@ -1,5 +1,9 @@
 use eyre::{eyre, Result};
 use std::collections::{HashMap, HashSet};
+use utils::input::InputContext;
+use utils::progress;
+use utils::timing;
+use utils::visualize::Frames;
 
 #[derive(Clone, Debug, PartialEq)]
 enum Map {
@@ -32,16 +36,21 @@ impl Hike {
 fn parse_input(input: &str) -> Result<Vec<Vec<Map>>> {
     input
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(i, l)| {
             l.chars()
-                .map(|c| match c {
-                    '#' => Ok(Map::Tree),
-                    '.' => Ok(Map::Path),
-                    '^' => Ok(Map::SlopeUp),
-                    'v' => Ok(Map::SlopeDown),
-                    '<' => Ok(Map::SlopeLeft),
-                    '>' => Ok(Map::SlopeRight),
-                    _ => Err(eyre!("unknown map char {}", c)),
+                .enumerate()
+                .map(|(j, c)| {
+                    match c {
+                        '#' => Ok(Map::Tree),
+                        '.' => Ok(Map::Path),
+                        '^' => Ok(Map::SlopeUp),
+                        'v' => Ok(Map::SlopeDown),
+                        '<' => Ok(Map::SlopeLeft),
+                        '>' => Ok(Map::SlopeRight),
+                        _ => Err(eyre!("unknown map char {}", c)),
+                    }
+                    .column_context(i, j, l)
                 })
                 .collect::<Result<Vec<Map>>>()
         })
@@ -72,33 +81,36 @@ fn next_steps(map: &[Vec<Map>], pos: (usize, usize)) -> Vec<(usize, usize)> {
     steps
 }
 
-pub fn solve_one(input: &str) -> Result<String> {
-    let map = parse_input(input)?;
-
-    let start = (
+fn start(map: &[Vec<Map>]) -> Result<(usize, usize)> {
+    Ok((
         0,
         map[0]
             .iter()
             .enumerate()
             .find_map(|(j, m)| if *m == Map::Path { Some(j) } else { None })
             .ok_or(eyre!("no path on top row"))?,
-    );
+    ))
+}
 
-    let mut hikes = vec![Hike::new(start)];
-    let mut max = 0;
+// Tiles of the longest hike to the bottom row, including the start and end.
+fn longest_hike(map: &[Vec<Map>]) -> Result<HashSet<(usize, usize)>> {
+    let mut hikes = vec![Hike::new(start(map)?)];
+    let mut longest = HashSet::new();
     while !hikes.is_empty() {
         hikes = hikes
             .into_iter()
             .filter(|h| {
                 if h.pos.0 == map.len() - 1 {
-                    max = max.max(h.seen.len());
+                    if h.seen.len() > longest.len() {
+                        longest = h.seen.clone();
+                    }
                     false
                 } else {
                     true
                 }
             })
             .flat_map(|h| {
-                next_steps(&map, h.pos)
+                next_steps(map, h.pos)
                     .into_iter()
                     .filter_map(move |next_pos| {
                         if h.seen.contains(&next_pos) {
@@ -118,7 +130,16 @@ pub fn solve_one(input: &str) -> Result<String> {
             .collect();
     }
 
-    Ok((max - 1).to_string())
+    if longest.is_empty() {
+        return Err(eyre!("no hike reaches the bottom row"));
+    }
+    Ok(longest)
+}
+
+pub fn solve_one(input: &str) -> Result<String> {
+    let map = parse_input(input)?;
+    timing::parsed();
+    Ok((longest_hike(&map)?.len() - 1).to_string())
 }
 
 #[derive(Clone, Debug)]
@@ -207,6 +228,7 @@ impl HikeNodes {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let mut map = parse_input(input)?;
+    timing::parsed();
 
     map.iter_mut().for_each(|row| {
         row.iter_mut().for_each(|p| match p {
@@ -221,7 +243,12 @@ pub fn solve_two(input: &str) -> Result<String> {
 
     let mut hikes = vec![HikeNodes::new()];
     let mut max = 0;
+    let mut frontier = 0;
     while !hikes.is_empty() {
+        frontier += 1;
+        progress::report_value(frontier, None, || {
+            format!("{} hikes, longest so far {}", hikes.len(), max)
+        });
         hikes = hikes
             .into_iter()
             .flat_map(|hike| {
@@ -258,3 +285,48 @@ pub fn solve_two(input: &str) -> Result<String> {
 
     Ok(max.to_string())
 }
+
+/// Frames of the longest hike for part one, drawn from the start a section at a time.
+pub fn visualize(input: &str) -> Result<Frames> {
+    const STEPS: usize = 100;
+
+    let map = parse_input(input)?;
+    let hike = longest_hike(&map)?;
+
+    // walk the hike from the start, the tiles either side of a path are trees so each tile only
+    // has the next tile of the hike as an unvisited neighbour
+    let mut order = vec![start(&map)?];
+    let mut visited = HashSet::from([order[0]]);
+    while let Some(&(i, j)) = order.last() {
+        let next = [
+            (i.wrapping_sub(1), j),
+            (i + 1, j),
+            (i, j.wrapping_sub(1)),
+            (i, j + 1),
+        ]
+        .into_iter()
+        .find(|p| hike.contains(p) && !visited.contains(p));
+        match next {
+            Some(p) => {
+                visited.insert(p);
+                order.push(p);
+            }
+            None => break,
+        }
+    }
+
+    // tree, path, slope, hike
+    let mut frames = Frames::new(map[0].len(), map.len(), &["#264", "#dca", "#a86", "#f33"]);
+    let mut on_hike = vec![vec![false; map[0].len()]; map.len()];
+    let tile = |i: usize, j: usize| match map[i][j] {
+        Map::Tree => 0,
+        Map::Path => 1,
+        _ => 2,
+    };
+    frames.push(tile);
+    for chunk in order.chunks(order.len().div_ceil(STEPS)) {
+        chunk.iter().for_each(|&(i, j)| on_hike[i][j] = true);
+        frames.push(|i, j| if on_hike[i][j] { 3 } else { tile(i, j) });
+    }
+    Ok(frames)
+}
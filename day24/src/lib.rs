@@ -1,37 +1,42 @@
 use eyre::{eyre, Result};
+use utils::input::InputContext;
+use utils::timing;
 
 const OFFSET: i128 = 300_000_000_000_000;
 const AREA: f64 = 100_000_000_000_000.0;
 
+#[allow(clippy::type_complexity)]
+fn parse_stone(l: &str) -> Result<((i128, i128, i128), (i128, i128, i128))> {
+    let (pos, vel) = l.split_once(" @ ").ok_or(eyre!("missing ' @ '"))?;
+    let pos = pos
+        .split(", ")
+        .map(|v| Ok(v.parse::<i128>()?))
+        .collect::<Result<Vec<i128>>>()?;
+    let vel = vel
+        .split(", ")
+        .map(|v| Ok(v.parse::<i128>()?))
+        .collect::<Result<Vec<i128>>>()?;
+    if pos.len() != 3 || vel.len() != 3 {
+        eyre::bail!("expected 3 position and velocity co-ords");
+    }
+    Ok((
+        (pos[0] - OFFSET, pos[1] - OFFSET, pos[2] - OFFSET),
+        (vel[0], vel[1], vel[2]),
+    ))
+}
+
 #[allow(clippy::type_complexity)]
 fn parse_input(input: &str) -> Result<Vec<((i128, i128, i128), (i128, i128, i128))>> {
     input
         .lines()
-        .map(|l| {
-            let (pos, vel) = l
-                .split_once(" @ ")
-                .ok_or(eyre!("missing ' @ ' in line {}", l))?;
-            let pos = pos
-                .split(", ")
-                .map(|v| Ok(v.parse::<i128>()?))
-                .collect::<Result<Vec<i128>>>()?;
-            let vel = vel
-                .split(", ")
-                .map(|v| Ok(v.parse::<i128>()?))
-                .collect::<Result<Vec<i128>>>()?;
-            if pos.len() != 3 || vel.len() != 3 {
-                eyre::bail!("expected 3 position and velocity co-ords - {}", l);
-            }
-            Ok((
-                (pos[0] - OFFSET, pos[1] - OFFSET, pos[2] - OFFSET),
-                (vel[0], vel[1], vel[2]),
-            ))
-        })
+        .enumerate()
+        .map(|(i, l)| parse_stone(l).line_context(i, l))
         .collect()
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
     let stones = &parse_input(input)?;
+    timing::parsed();
     Ok((0..stones.len() - 1)
         .flat_map(|a| (a + 1..stones.len()).map(move |b| (stones[a], stones[b])))
         .filter(|((a_pos, a_vel), (b_pos, b_vel))| {
@@ -70,6 +75,7 @@ pub fn solve_one(input: &str) -> Result<String> {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let stones = &parse_input(input)?;
+    timing::parsed();
 
     let mut pos_sum = 0;
     'z: for z in -1000..=1000 {
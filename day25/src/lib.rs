@@ -1,19 +1,22 @@
 use eyre::{eyre, Result};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet};
+use utils::input::InputContext;
+use utils::timing;
 
-fn parse_input(input: &str) -> Result<HashMap<String, Vec<String>>> {
+fn parse_line(l: &str) -> Result<(String, Vec<String>)> {
+    let (k, v) = l.split_once(": ").ok_or(eyre!("missing ': '"))?;
+    Ok((k.to_string(), v.split(' ').map(|s| s.to_string()).collect()))
+}
+
+fn parse_input(input: &str) -> Result<BTreeMap<String, Vec<String>>> {
     input
         .lines()
-        .map(|l| {
-            let (k, v) = l
-                .split_once(": ")
-                .ok_or(eyre!("missing ': ' in line {}", l))?;
-            Ok((k.to_string(), v.split(' ').map(|s| s.to_string()).collect()))
-        })
+        .enumerate()
+        .map(|(i, l)| parse_line(l).line_context(i, l))
         .collect()
 }
 
-fn fill_conns(mut conns: HashMap<String, Vec<String>>) -> HashMap<String, Vec<String>> {
+fn fill_conns(mut conns: BTreeMap<String, Vec<String>>) -> BTreeMap<String, Vec<String>> {
     conns.clone().into_iter().for_each(|(k, v)| {
         v.into_iter().for_each(|v| {
             conns
@@ -29,66 +32,64 @@ fn fill_conns(mut conns: HashMap<String, Vec<String>>) -> HashMap<String, Vec<St
 
 #[derive(Clone, Debug)]
 struct Split {
-    g1: HashSet<String>,
-    g2: HashSet<String>,
-    c: HashMap<String, HashSet<String>>,
+    g1: BTreeSet<String>,
+    g2: BTreeSet<String>,
+    c: BTreeMap<String, BTreeSet<String>>,
 }
 
-pub fn solve_one(input: &str) -> Result<String> {
-    let conns = fill_conns(parse_input(input)?);
-
-    let mut split = {
-        let mut g1 = conns.keys().cloned().collect::<HashSet<_>>();
-        let (k, v) = conns.iter().next().ok_or(eyre!("conns empty"))?;
-        let mut g2 = HashSet::new();
-        g2.insert(g1.take(k).ok_or(eyre!("{} missing from g1", k))?);
-        let mut c = HashMap::new();
-        v.iter().for_each(|v| {
-            c.insert(v.clone(), {
-                let mut s = HashSet::new();
-                s.insert(k.clone());
-                s
-            });
-        });
-        Split { g1, g2, c }
+/// Grow a group from `start` one component at a time, always taking the component with the most
+/// connections into the group, until exactly 3 connections leave it. Returns the sizes of the
+/// group and the rest, or `None` if the group swallows every component first.
+fn split_from(conns: &BTreeMap<String, Vec<String>>, start: &str) -> Option<(usize, usize)> {
+    let mut split = Split {
+        g1: conns.keys().cloned().collect(),
+        g2: BTreeSet::new(),
+        c: BTreeMap::new(),
     };
+    split.g2.insert(split.g1.take(start)?);
+    for v in conns.get(start)? {
+        split
+            .c
+            .entry(v.clone())
+            .or_default()
+            .insert(start.to_string());
+    }
 
     while split.c.values().map(|v| v.len()).sum::<usize>() != 3 {
         let max_conns = split
             .c
             .iter()
             .max_by_key(|(_, v)| v.len())
-            .map(|(k, _)| k.clone())
-            .ok_or(eyre!("empty conns"))?;
-        split.g2.insert(
-            split
-                .g1
-                .take(&max_conns)
-                .ok_or(eyre!("{} missing from g1", &max_conns))?,
-        );
+            .map(|(k, _)| k.clone())?;
+        split.g2.insert(split.g1.take(&max_conns)?);
         split.c.remove(&max_conns);
-        conns
-            .get(&max_conns)
-            .ok_or(eyre!("{} missing from conns", &max_conns))?
-            .iter()
-            .for_each(|v| {
-                if split.g1.contains(v) {
-                    split
-                        .c
-                        .entry(v.clone())
-                        .and_modify(|s| {
-                            s.insert(max_conns.clone());
-                        })
-                        .or_insert({
-                            let mut s = HashSet::new();
-                            s.insert(max_conns.clone());
-                            s
-                        });
-                }
-            });
+        for v in conns.get(&max_conns)? {
+            if split.g1.contains(v) {
+                split
+                    .c
+                    .entry(v.clone())
+                    .or_default()
+                    .insert(max_conns.clone());
+            }
+        }
     }
+    Some((split.g1.len(), split.g2.len()))
+}
+
+pub fn solve_one(input: &str) -> Result<String> {
+    let conns = parse_input(input)?;
+    timing::parsed();
+    let conns = fill_conns(conns);
 
-    Ok((split.g1.len() * split.g2.len()).to_string())
+    // the greedy growth can take in a component from the other side early on and then never
+    // find the cut, so try each start in turn
+    let (g1, g2) = conns
+        .keys()
+        .find_map(|start| split_from(&conns, start))
+        .ok_or(eyre!(
+            "no split with 3 connections between the groups found"
+        ))?;
+    Ok((g1 * g2).to_string())
 }
 
 pub fn solve_two(_: &str) -> Result<String> {
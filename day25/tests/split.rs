@@ -0,0 +1,63 @@
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Input shaped like the real puzzle inputs, two groups of components that each have at least 4
+/// connections, joined by 3 connections. Returns the input and the expected answer.
+fn generate(seed: u64, size: usize) -> (String, usize) {
+    let mut state = seed;
+    let mut rand = |n: usize| {
+        state = state
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (state >> 33) as usize % n
+    };
+    let name = |i: usize| {
+        let c = |d: usize| (b'a' + (i / d % 26) as u8) as char;
+        format!("{}{}{}", c(676), c(26), c(1))
+    };
+    let split = size / 2 - size / 10 + rand(size / 5);
+    let mut edges = BTreeSet::new();
+    for (start, end) in [(0, split), (split, size)] {
+        let len = end - start;
+        for i in 0..len {
+            edges.insert((start + i, start + (i + 1) % len));
+        }
+        let mut degree = vec![2; len];
+        for i in 0..len {
+            while degree[i] < 4 {
+                let j = rand(len);
+                let edge = (start + i.min(j), start + i.max(j));
+                if i != j && !edges.contains(&edge) && !edges.contains(&(edge.1, edge.0)) {
+                    edges.insert(edge);
+                    degree[i] += 1;
+                    degree[j] += 1;
+                }
+            }
+        }
+    }
+    for _ in 0..3 {
+        edges.insert((rand(split), split + rand(size - split)));
+    }
+    // each component is listed once, with the connections not already listed
+    let mut listed = BTreeMap::new();
+    for &(a, b) in &edges {
+        listed.entry(a).or_insert_with(Vec::new).push(name(b));
+    }
+    let input = listed
+        .iter()
+        .map(|(&a, conns)| format!("{}: {}", name(a), conns.join(" ")))
+        .collect::<Vec<String>>()
+        .join("\n");
+    (input, split * (size - split))
+}
+
+#[test]
+fn finds_the_cut() {
+    for seed in 0..20 {
+        let (input, answer) = generate(seed, 400);
+        assert_eq!(
+            day25::solve_one(&input).unwrap(),
+            answer.to_string(),
+            "seed {seed}"
+        );
+    }
+}
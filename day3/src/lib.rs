@@ -1,118 +1,153 @@
 use eyre::Result;
-use std::collections::HashSet;
+use std::collections::HashMap;
 use std::ops::Range;
+use utils::input::InputContext;
+use utils::timing;
 
-pub fn solve_one(input: &str) -> Result<String> {
-    let val_ranges = get_val_ranges(input)?;
+/// Number in the schematic, `columns` is the range of columns its digits are in.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Number {
+    pub value: u64,
+    pub row: usize,
+    pub columns: Range<usize>,
+}
 
-    let to_check = input
-        .lines()
-        .enumerate()
-        .flat_map(|(i, l)| {
-            // find none numeric none '.' in each line
-            l.match_indices(|c: char| !c.is_numeric() && c != '.')
-                // get surrounding indicies
-                .flat_map(move |(j, _)| get_surrounding(i, j))
-        })
-        // collect indicies in set to remove duplicates
-        .collect::<HashSet<(usize, usize)>>();
+/// Symbol in the schematic, anything other than a digit or '.'.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub symbol: char,
+    pub row: usize,
+    pub column: usize,
+}
 
-    Ok(to_check
-        .into_iter()
-        // get (number, line, range_on_line) for each indice
-        .filter_map(|(i, j)| {
-            val_ranges.get(i).and_then(|l| {
-                l.iter()
-                    .find(|(_, r)| r.contains(&j))
-                    .map(|(v, r)| (v, i, r))
-            })
-        })
-        // collect in set to remove duplicates
-        .collect::<HashSet<(&i64, usize, &Range<usize>)>>()
-        .iter()
-        .map(|(&v, _, _)| v)
-        .sum::<i64>()
-        .to_string())
+/// Engine schematic, with which numbers are next to which symbols found once when it is parsed.
+#[derive(Clone, Debug, Default)]
+pub struct Schematic {
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    // indices of the numbers next to each symbol
+    symbol_numbers: Vec<Vec<usize>>,
+    // indices of the symbols next to each number
+    number_symbols: Vec<Vec<usize>>,
 }
+impl Schematic {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut schematic = Self::default();
+        let mut symbol_at = HashMap::new();
+        for (row, l) in input.lines().enumerate() {
+            let mut digits = String::new();
+            // a '.' past the end of the line ends a number at the end of the line
+            for (column, c) in l.chars().chain(['.']).enumerate() {
+                if c.is_ascii_digit() {
+                    digits.push(c);
+                    continue;
+                }
+                if !digits.is_empty() {
+                    let start = column - digits.len();
+                    let value = digits
+                        .parse::<u64>()
+                        .map_err(eyre::Report::from)
+                        .column_context(row, start, l)?;
+                    schematic.numbers.push(Number {
+                        value,
+                        row,
+                        columns: start..column,
+                    });
+                    digits.clear();
+                }
+                if c != '.' {
+                    symbol_at.insert((row, column), schematic.symbols.len());
+                    schematic.symbols.push(Symbol {
+                        symbol: c,
+                        row,
+                        column,
+                    });
+                }
+            }
+        }
 
-pub fn solve_two(input: &str) -> Result<String> {
-    let val_ranges = get_val_ranges(input)?;
+        schematic.symbol_numbers = vec![vec![]; schematic.symbols.len()];
+        for (n, number) in schematic.numbers.iter().enumerate() {
+            let mut symbols = vec![];
+            for row in number.row.saturating_sub(1)..=number.row + 1 {
+                for column in number.columns.start.saturating_sub(1)..=number.columns.end {
+                    if let Some(&s) = symbol_at.get(&(row, column)) {
+                        symbols.push(s);
+                        schematic.symbol_numbers[s].push(n);
+                    }
+                }
+            }
+            schematic.number_symbols.push(symbols);
+        }
+        Ok(schematic)
+    }
 
-    let gears = input
-        .lines()
-        .enumerate()
-        .flat_map(|(i, l)| l.match_indices('*').map(move |(j, _)| (i, j)))
-        .collect::<Vec<(usize, usize)>>();
+    /// Numbers next to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(|(_, symbols)| !symbols.is_empty())
+            .map(|(number, _)| number)
+    }
 
-    Ok(gears
-        .into_iter()
-        .map(|(i, j)| {
-            let vals = get_surrounding(i, j)
-                .into_iter()
-                .filter_map(|(i, j)| {
-                    val_ranges
-                        .get(i)
-                        .and_then(|l| l.iter().find(|(_, r)| r.contains(&j)))
-                        .map(|(v, r)| (v, i, r))
-                })
-                .collect::<HashSet<(&i64, usize, &Range<usize>)>>()
-                .into_iter()
-                .map(|(v, _, _)| v)
-                .collect::<Vec<&i64>>();
-            if vals.len() == 2 {
-                vals[0] * vals[1]
-            } else {
-                0
-            }
-        })
-        .sum::<i64>()
-        .to_string())
+    /// Numbers next to a `symbol` of this kind, each number once however many of them it is next
+    /// to.
+    pub fn numbers_adjacent_to_kind(&self, symbol: char) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .zip(&self.number_symbols)
+            .filter(move |(_, symbols)| symbols.iter().any(|&s| self.symbols[s].symbol == symbol))
+            .map(|(number, _)| number)
+    }
+
+    /// Symbols next to the number at `number` in [`Schematic::numbers`].
+    pub fn symbols_adjacent_to_number_at(&self, number: usize) -> impl Iterator<Item = &Symbol> {
+        self.number_symbols
+            .get(number)
+            .into_iter()
+            .flatten()
+            .map(|&s| &self.symbols[s])
+    }
+
+    /// Numbers next to the symbol at `symbol` in [`Schematic::symbols`].
+    pub fn numbers_adjacent_to_symbol_at(&self, symbol: usize) -> impl Iterator<Item = &Number> {
+        self.symbol_numbers
+            .get(symbol)
+            .into_iter()
+            .flatten()
+            .map(|&n| &self.numbers[n])
+    }
+
+    /// Each `symbol` next to exactly `k` numbers, with its ratio, the product of the numbers.
+    pub fn gears(&self, symbol: char, k: usize) -> impl Iterator<Item = (&Symbol, u64)> {
+        self.symbols
+            .iter()
+            .zip(&self.symbol_numbers)
+            .filter(move |(s, numbers)| s.symbol == symbol && numbers.len() == k)
+            .map(|(s, numbers)| {
+                let ratio = numbers.iter().map(|&n| self.numbers[n].value).product();
+                (s, ratio)
+            })
+    }
 }
 
-#[allow(clippy::type_complexity)]
-fn get_val_ranges(input: &str) -> Result<Vec<Vec<(i64, Range<usize>)>>> {
-    input
-        .lines()
-        .map(|l| {
-            let mut v_r = vec![];
-            let mut rem = l;
-            let mut skip = 0;
-            while let Some(s) = rem.find(char::is_numeric) {
-                let mut e = s + 1;
-                while rem.len() > e
-                    && rem
-                        .chars()
-                        .nth(e)
-                        .ok_or(eyre::eyre!("missing char"))?
-                        .is_numeric()
-                {
-                    e += 1;
-                }
-                v_r.push((rem[s..e].parse::<i64>()?, skip + s..skip + e));
-                skip += e;
-                rem = &rem[e..];
-            }
-            Ok(v_r)
-        })
-        .collect::<Result<Vec<Vec<(i64, Range<usize>)>>>>()
+pub fn solve_one(input: &str) -> Result<String> {
+    let schematic = Schematic::parse(input)?;
+    timing::parsed();
+    Ok(schematic
+        .part_numbers()
+        .map(|n| n.value)
+        .sum::<u64>()
+        .to_string())
 }
 
-fn get_surrounding(i: usize, j: usize) -> Vec<(usize, usize)> {
-    let i_sub = i.checked_sub(1);
-    let i_add = i.checked_add(1);
-    let j_sub = j.checked_sub(1);
-    let j_add = j.checked_add(1);
-    vec![
-        i_sub.and_then(|is| j_sub.map(|js| (is, js))),
-        i_sub.map(|is| (is, j)),
-        i_sub.and_then(|is| j_add.map(|ja| (is, ja))),
-        j_sub.map(|js| (i, js)),
-        j_add.map(|ja| (i, ja)),
-        i_add.and_then(|ia| j_sub.map(|js| (ia, js))),
-        i_add.map(|ia| (ia, j)),
-        i_add.and_then(|ia| j_add.map(|ja| (ia, ja))),
-    ]
-    .into_iter()
-    .flatten()
-    .collect()
+pub fn solve_two(input: &str) -> Result<String> {
+    let schematic = Schematic::parse(input)?;
+    timing::parsed();
+    Ok(schematic
+        .gears('*', 2)
+        .map(|(_, ratio)| ratio)
+        .sum::<u64>()
+        .to_string())
 }
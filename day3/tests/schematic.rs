@@ -0,0 +1,66 @@
+use day3::Schematic;
+
+const EXAMPLE: &str = "467..114..
+...*......
+..35..633.
+......#...
+617*......
+.....+.58.
+..592.....
+......755.
+...$.*....
+.664.598..";
+
+fn values<'a>(numbers: impl Iterator<Item = &'a day3::Number>) -> Vec<u64> {
+    numbers.map(|n| n.value).collect()
+}
+
+#[test]
+fn adjacency() {
+    let schematic = Schematic::parse(EXAMPLE).unwrap();
+    assert_eq!(
+        values(schematic.part_numbers()),
+        [467, 35, 633, 617, 592, 755, 664, 598]
+    );
+    assert_eq!(
+        values(schematic.numbers_adjacent_to_kind('*')),
+        [467, 35, 617, 755, 598]
+    );
+    assert_eq!(values(schematic.numbers_adjacent_to_kind('$')), [664]);
+    assert_eq!(values(schematic.numbers_adjacent_to_kind('x')), []);
+
+    assert_eq!(
+        schematic
+            .symbols_adjacent_to_number_at(0)
+            .map(|s| (s.symbol, s.row, s.column))
+            .collect::<Vec<_>>(),
+        [('*', 1, 3)]
+    );
+    assert_eq!(schematic.symbols_adjacent_to_number_at(1).count(), 0);
+    assert_eq!(schematic.symbols_adjacent_to_number_at(99).count(), 0);
+
+    assert_eq!(
+        values(schematic.numbers_adjacent_to_symbol_at(0)),
+        [467, 35]
+    );
+    assert_eq!(values(schematic.numbers_adjacent_to_symbol_at(2)), [617]);
+    assert_eq!(values(schematic.numbers_adjacent_to_symbol_at(99)), []);
+}
+
+fn ratios(schematic: &Schematic, symbol: char, k: usize) -> Vec<u64> {
+    schematic.gears(symbol, k).map(|(_, ratio)| ratio).collect()
+}
+
+#[test]
+fn gears() {
+    let schematic = Schematic::parse(EXAMPLE).unwrap();
+    assert_eq!(ratios(&schematic, '*', 2), [16345, 451490]);
+    assert_eq!(ratios(&schematic, '*', 1), [617]);
+    assert_eq!(ratios(&schematic, '*', 0), []);
+    assert_eq!(ratios(&schematic, '#', 1), [633]);
+
+    let schematic = Schematic::parse("2.3\n.*.\n4..\n..*\n").unwrap();
+    assert_eq!(ratios(&schematic, '*', 3), [24]);
+    assert_eq!(ratios(&schematic, '*', 2), []);
+    assert_eq!(ratios(&schematic, '*', 0), [1]);
+}
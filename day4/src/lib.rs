@@ -1,52 +1,162 @@
 use eyre::{eyre, Result};
+pub use num_bigint::BigUint;
 use std::collections::HashSet;
+use utils::input::InputContext;
+use utils::{explain, timing};
 
-fn parse_input(input: &str) -> Result<Vec<(HashSet<i64>, HashSet<i64>)>> {
-    input
-        .lines()
-        .map(|l| {
-            let (_, card) = l.split_once(": ").ok_or(eyre!("missing ': '"))?;
-            let (win, have) = card.split_once(" | ").ok_or(eyre!("missing ' | '"))?;
-            Ok((
-                win.split(' ')
-                    .filter(|v| !v.is_empty())
-                    .map(|num| Ok(num.parse::<i64>()?))
-                    .collect::<Result<HashSet<i64>>>()?,
-                have.split(' ')
-                    .filter(|v| !v.is_empty())
-                    .map(|num| Ok(num.parse::<i64>()?))
-                    .collect::<Result<HashSet<i64>>>()?,
-            ))
-        })
-        .collect::<Result<Vec<(HashSet<i64>, HashSet<i64>)>>>()
+/// Scratchcard, with the winning numbers and the numbers it has.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Card {
+    pub id: u64,
+    pub winning: HashSet<u64>,
+    pub have: HashSet<u64>,
 }
+impl Card {
+    /// Number of the card's numbers that are winning numbers.
+    pub fn matches(&self) -> usize {
+        self.winning.intersection(&self.have).count()
+    }
 
-pub fn solve_one(input: &str) -> Result<String> {
-    Ok(parse_input(input)?
-        .iter()
-        .map(|c| {
-            let win_count = c.0.intersection(&c.1).count() as u32;
-            if win_count == 0 {
-                0
-            } else {
-                2_i32.pow(win_count - 1)
+    /// 1 point for the first match, doubled for each match after, so any number of matches fit.
+    pub fn points(&self) -> BigUint {
+        match self.matches() {
+            0 => BigUint::default(),
+            m => BigUint::from(1_u8) << (m - 1),
+        }
+    }
+}
+
+fn parse_numbers(numbers: &str) -> Result<HashSet<u64>> {
+    numbers
+        .split(' ')
+        .filter(|v| !v.is_empty())
+        .map(|num| Ok(num.parse::<u64>()?))
+        .collect()
+}
+
+fn parse_card(line: &str) -> Result<Card> {
+    let (id, card) = line.split_once(": ").ok_or(eyre!("missing ': '"))?;
+    let id = id
+        .strip_prefix("Card")
+        .ok_or(eyre!("missing 'Card' prefix"))?
+        .trim()
+        .parse::<u64>()?;
+    let (win, have) = card.split_once(" | ").ok_or(eyre!("missing ' | '"))?;
+    Ok(Card {
+        id,
+        winning: parse_numbers(win)?,
+        have: parse_numbers(have)?,
+    })
+}
+
+/// Pile of scratchcards with the copies each card wins, cards are referred to by their index in
+/// [`Scratchcards::cards`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Scratchcards {
+    pub cards: Vec<Card>,
+    matches: Vec<usize>,
+    // instances of each card, the original and the copies won
+    copies: Vec<u64>,
+}
+impl Scratchcards {
+    /// Play the cards, each card's matches win a copy of that many following cards.
+    pub fn new(cards: Vec<Card>) -> Result<Self> {
+        let matches = cards.iter().map(Card::matches).collect::<Vec<usize>>();
+        let mut copies = vec![1_u64; cards.len()];
+        for (i, m) in matches.iter().enumerate() {
+            for j in i + 1..copies.len().min(i + 1 + m) {
+                copies[j] = copies[j]
+                    .checked_add(copies[i])
+                    .ok_or(eyre!("too many copies of card {}", cards[j].id))?;
             }
+        }
+        Ok(Self {
+            cards,
+            matches,
+            copies,
         })
-        .sum::<i32>()
-        .to_string())
+    }
+
+    pub fn parse(input: &str) -> Result<Self> {
+        let cards = input
+            .lines()
+            .enumerate()
+            .map(|(i, l)| parse_card(l).line_context(i, l))
+            .collect::<Result<Vec<Card>>>()?;
+        Self::new(cards)
+    }
+
+    pub fn matches(&self, card: usize) -> usize {
+        self.matches.get(card).copied().unwrap_or_default()
+    }
+
+    /// Instances of the card, the original and the copies won.
+    pub fn copies(&self, card: usize) -> u64 {
+        self.copies.get(card).copied().unwrap_or_default()
+    }
+
+    /// Cards that won copies of the card, with how many copies each won.
+    pub fn won_from(&self, card: usize) -> Vec<(usize, u64)> {
+        (0..card.min(self.cards.len()))
+            .filter(|&i| i + self.matches[i] >= card)
+            .map(|i| (i, self.copies[i]))
+            .collect()
+    }
+
+    /// Points of every original card.
+    pub fn points(&self) -> BigUint {
+        self.cards.iter().map(Card::points).sum()
+    }
+
+    /// Instances of every card.
+    pub fn total_copies(&self) -> Result<u64> {
+        self.copies
+            .iter()
+            .try_fold(0_u64, |total, &c| total.checked_add(c))
+            .ok_or(eyre!("too many copies"))
+    }
+}
+
+pub fn solve_one(input: &str) -> Result<String> {
+    let cards = Scratchcards::parse(input)?;
+    timing::parsed();
+    explain::section("card matches");
+    for card in &cards.cards {
+        explain::line(|| {
+            format!(
+                "card {}: {} matches, {} points",
+                card.id,
+                card.matches(),
+                card.points()
+            )
+        });
+    }
+    Ok(cards.points().to_string())
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    let cards = parse_input(input)?;
-    let wins = cards
-        .iter()
-        .map(|c| c.0.intersection(&c.1).count())
-        .collect::<Vec<usize>>();
-    let mut count = vec![1; cards.len()];
-    for (i, w) in wins.iter().enumerate() {
-        for j in i + 1..count.len().min(i + 1 + w) {
-            count[j] += count[i];
-        }
+    let cards = Scratchcards::parse(input)?;
+    timing::parsed();
+    explain::section("card copies");
+    for (i, card) in cards.cards.iter().enumerate() {
+        explain::line(|| {
+            let won_from = cards
+                .won_from(i)
+                .into_iter()
+                .map(|(j, c)| format!("{} from card {}", c, cards.cards[j].id))
+                .collect::<Vec<String>>();
+            format!(
+                "card {}: {} matches, {} copies, won {}",
+                card.id,
+                cards.matches(i),
+                cards.copies(i),
+                if won_from.is_empty() {
+                    "none".to_string()
+                } else {
+                    won_from.join(", ")
+                }
+            )
+        });
     }
-    Ok(count.iter().sum::<i64>().to_string())
+    Ok(cards.total_copies()?.to_string())
 }
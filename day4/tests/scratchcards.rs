@@ -0,0 +1,42 @@
+use day4::{BigUint, Scratchcards};
+
+const EXAMPLE: &str = "Card 1: 41 48 83 86 17 | 83 86  6 31 17  9 48 53
+Card 2: 13 32 20 16 61 | 61 30 68 82 17 32 24 19
+Card 3:  1 21 53 59 44 | 69 82 63 72 16 21 14  1
+Card 4: 41 92 73 84 69 | 59 84 76 51 58  5 54 83
+Card 5: 87 83 26 28 32 | 88 30 70 12 93 22 82 36
+Card 6: 31 18 13 56 72 | 74 77 10 23 35 67 36 11";
+
+#[test]
+fn example() {
+    let cards = Scratchcards::parse(EXAMPLE).unwrap();
+    assert_eq!(
+        (0..6).map(|i| cards.matches(i)).collect::<Vec<usize>>(),
+        [4, 2, 2, 1, 0, 0]
+    );
+    assert_eq!(
+        (0..6).map(|i| cards.copies(i)).collect::<Vec<u64>>(),
+        [1, 2, 4, 8, 14, 1]
+    );
+    assert_eq!(cards.total_copies().unwrap(), 30);
+    assert_eq!(cards.points(), BigUint::from(13_u8));
+
+    // card 5 gets a copy from every instance of cards 1, 3 and 4
+    assert_eq!(cards.won_from(4), [(0, 1), (2, 4), (3, 8)]);
+    assert_eq!(cards.won_from(0), []);
+    assert_eq!(cards.won_from(5), []);
+    assert_eq!(cards.matches(6), 0);
+    assert_eq!(cards.copies(6), 0);
+
+    assert_eq!(day4::solve_one(EXAMPLE).unwrap(), "13");
+    assert_eq!(day4::solve_two(EXAMPLE).unwrap(), "30");
+}
+
+#[test]
+fn points_past_u64() {
+    let numbers = (1..=70).map(|n| n.to_string()).collect::<Vec<String>>();
+    let card = format!("Card 1: {} | {}", numbers.join(" "), numbers.join(" "));
+    let cards = Scratchcards::parse(&card).unwrap();
+    assert_eq!(cards.points(), BigUint::from(1_u8) << 69);
+    assert_eq!(cards.points().to_string(), "590295810358705651712");
+}
@@ -1,177 +1,266 @@
 use core::ops::Range;
-use eyre::{eyre, Result};
+use eyre::{eyre, Result, WrapErr};
+use utils::input::InputContext;
+use utils::timing;
 
-macro_rules! map {
-    ($from:ty, $to:ty, $map:ident) => {
-        impl $to {
-            fn new(v: i64) -> Self {
-                Self(v)
+/// Piece of a [`PiecewiseMap`], values in `range` are shifted by `offset`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Piece {
+    pub range: Range<i64>,
+    pub offset: i64,
+}
+
+/// Piecewise-linear function made of shifted ranges, values outside every piece map to
+/// themselves. The pieces are sorted and do not overlap.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PiecewiseMap {
+    pieces: Vec<Piece>,
+}
+impl PiecewiseMap {
+    /// Map from pieces in any order, which must not overlap.
+    pub fn new(mut pieces: Vec<Piece>) -> Result<Self> {
+        pieces.retain(|p| !p.range.is_empty());
+        pieces.sort_by_key(|p| p.range.start);
+        if let Some(w) = pieces
+            .windows(2)
+            .find(|w| w[0].range.end > w[1].range.start)
+        {
+            eyre::bail!("overlapping ranges {:?} and {:?}", w[0].range, w[1].range);
+        }
+        Ok(Self::normalized(pieces))
+    }
+
+    /// Drop pieces that do not shift and merge touching pieces with the same offset, so equal
+    /// functions compare equal. `pieces` must be sorted and not overlap.
+    fn normalized(pieces: Vec<Piece>) -> Self {
+        let mut merged: Vec<Piece> = vec![];
+        for p in pieces.into_iter().filter(|p| p.offset != 0) {
+            match merged.last_mut() {
+                Some(last) if last.range.end == p.range.start && last.offset == p.offset => {
+                    last.range.end = p.range.end;
+                }
+                _ => merged.push(p),
+            }
+        }
+        Self { pieces: merged }
+    }
+
+    pub fn pieces(&self) -> &[Piece] {
+        &self.pieces
+    }
+
+    /// Pieces with the gaps between them filled by pieces that do not shift, covering every value.
+    fn cover(&self) -> Vec<Piece> {
+        let mut cover = vec![];
+        let mut start = i64::MIN;
+        for p in &self.pieces {
+            if start < p.range.start {
+                cover.push(Piece {
+                    range: start..p.range.start,
+                    offset: 0,
+                });
             }
+            cover.push(p.clone());
+            start = p.range.end;
+        }
+        if start < i64::MAX {
+            cover.push(Piece {
+                range: start..i64::MAX,
+                offset: 0,
+            });
         }
-        impl $from {
-            fn map(&self, maps: &Maps) -> $to {
-                <$to>::new(
-                    maps.$map
-                        .iter()
-                        .find(|(r, _)| r.contains(&self.0))
-                        .map(|(_, d)| self.0 + d)
-                        .unwrap_or(self.0),
-                )
+        cover
+    }
+
+    pub fn apply(&self, value: i64) -> i64 {
+        self.pieces
+            .iter()
+            .find(|p| p.range.contains(&value))
+            .map_or(value, |p| value + p.offset)
+    }
+
+    /// Values the range maps to, as ranges in the order of the values they come from.
+    pub fn apply_range(&self, range: &Range<i64>) -> Vec<Range<i64>> {
+        self.cover()
+            .into_iter()
+            .filter_map(|p| {
+                let start = p.range.start.max(range.start);
+                let end = p.range.end.min(range.end);
+                (start < end).then(|| start + p.offset..end + p.offset)
+            })
+            .collect()
+    }
+
+    /// Values mapping into the range, the inverse of [`PiecewiseMap::apply_range`].
+    pub fn preimage(&self, range: &Range<i64>) -> Vec<Range<i64>> {
+        self.cover()
+            .into_iter()
+            .filter_map(|p| {
+                let start = (p.range.start + p.offset).max(range.start) - p.offset;
+                let end = (p.range.end + p.offset).min(range.end) - p.offset;
+                (start < end).then_some(start..end)
+            })
+            .collect()
+    }
+
+    /// Values mapping to `value`, more than one if pieces map onto the same values.
+    pub fn inverse(&self, value: i64) -> Vec<i64> {
+        self.preimage(&(value..value + 1))
+            .into_iter()
+            .map(|r| r.start)
+            .collect()
+    }
+
+    /// Map applying `self` then `next`.
+    pub fn then(&self, next: &PiecewiseMap) -> PiecewiseMap {
+        let next_cover = next.cover();
+        let mut pieces = vec![];
+        for p in self.cover() {
+            // split this piece's image by the pieces of next, back in this piece's values
+            let image = p.range.start + p.offset..p.range.end + p.offset;
+            for n in next_cover
+                .iter()
+                .filter(|n| n.range.start < image.end && image.start < n.range.end)
+            {
+                let start = image.start.max(n.range.start) - p.offset;
+                let end = image.end.min(n.range.end) - p.offset;
+                pieces.push(Piece {
+                    range: start..end,
+                    offset: p.offset + n.offset,
+                });
             }
         }
-    };
+        Self::normalized(pieces)
+    }
+}
+
+/// Map from one category to another, from a `seed-to-soil map:` section.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CategoryMap {
+    pub from: String,
+    pub to: String,
+    pub map: PiecewiseMap,
 }
-struct Seed(i64);
-map!(Seed, Soil, seed_soil);
-struct Soil(i64);
-map!(Soil, Fertilizer, soil_fert);
-struct Fertilizer(i64);
-map!(Fertilizer, Water, fert_watr);
-struct Water(i64);
-map!(Water, Light, watr_lght);
-struct Light(i64);
-map!(Light, Temperature, lght_temp);
-struct Temperature(i64);
-map!(Temperature, Humidity, temp_humd);
-struct Humidity(i64);
-map!(Humidity, Location, humd_loct);
-struct Location(i64);
-
-#[derive(Debug)]
-struct Maps {
-    seed_soil: Vec<(Range<i64>, i64)>,
-    soil_fert: Vec<(Range<i64>, i64)>,
-    fert_watr: Vec<(Range<i64>, i64)>,
-    watr_lght: Vec<(Range<i64>, i64)>,
-    lght_temp: Vec<(Range<i64>, i64)>,
-    temp_humd: Vec<(Range<i64>, i64)>,
-    humd_loct: Vec<(Range<i64>, i64)>,
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Almanac {
+    pub seeds: Vec<i64>,
+    /// maps in the order of their sections
+    pub maps: Vec<CategoryMap>,
 }
-impl Maps {
-    fn parse_input(maps: &str) -> Result<Self> {
-        fn gen_map(m: &str) -> Result<Vec<(Range<i64>, i64)>> {
-            m.trim()
-                .lines()
-                .skip(1)
-                .map(|l| {
-                    let l = l
-                        .split(' ')
-                        .map(|v| Ok(v.parse::<i64>()?))
-                        .collect::<Result<Vec<i64>>>()?;
-                    Ok((l[1]..l[1] + l[2], l[0] - l[1]))
-                })
-                .collect::<Result<Vec<(Range<i64>, i64)>>>()
+impl Almanac {
+    pub fn parse(input: &str) -> Result<Self> {
+        let mut lines = input.lines().enumerate();
+        let (i, first) = lines.next().ok_or(eyre!("missing seeds"))?;
+        let seeds = parse_seeds(first).line_context(i, first)?;
+        // (from, to, pieces) of each section
+        let mut sections: Vec<(String, String, Vec<Piece>)> = vec![];
+        for (i, l) in lines.filter(|(_, l)| !l.trim().is_empty()) {
+            if let Some(header) = l.trim().strip_suffix(" map:") {
+                let (from, to) = header
+                    .split_once("-to-")
+                    .ok_or(eyre!("expected '<from>-to-<to> map:'"))
+                    .line_context(i, l)?;
+                sections.push((from.to_string(), to.to_string(), vec![]));
+            } else {
+                let piece = parse_piece(l).line_context(i, l)?;
+                sections
+                    .last_mut()
+                    .ok_or(eyre!("range before any map header"))
+                    .line_context(i, l)?
+                    .2
+                    .push(piece);
+            }
+        }
+        let maps = sections
+            .into_iter()
+            .map(|(from, to, pieces)| {
+                let map = PiecewiseMap::new(pieces)
+                    .wrap_err_with(|| format!("in {}-to-{} map", from, to))?;
+                Ok(CategoryMap { from, to, map })
+            })
+            .collect::<Result<Vec<CategoryMap>>>()?;
+        Ok(Self { seeds, maps })
+    }
+
+    /// Seeds read as pairs of the start and length of ranges.
+    pub fn seed_ranges(&self) -> Result<Vec<Range<i64>>> {
+        if !self.seeds.len().is_multiple_of(2) {
+            eyre::bail!("odd number of seed values");
+        }
+        Ok(self.seeds.chunks(2).map(|c| c[0]..c[0] + c[1]).collect())
+    }
+
+    /// Single map from the `from` category to the `to` category, composed from the chain of maps
+    /// between them.
+    pub fn map_between(&self, from: &str, to: &str) -> Result<PiecewiseMap> {
+        let mut map = PiecewiseMap::default();
+        let mut category = from;
+        // each step uses a different map, so a cycle ends the search
+        for _ in 0..=self.maps.len() {
+            if category == to {
+                return Ok(map);
+            }
+            let next = self.maps.iter().find(|m| m.from == category).ok_or(eyre!(
+                "no map from {} on the way to {}",
+                category,
+                to
+            ))?;
+            map = map.then(&next.map);
+            category = &next.to;
         }
-        let mut maps = maps.split("\n\n");
-        Ok(Self {
-            seed_soil: gen_map(maps.next().ok_or(eyre!("missing next"))?)?,
-            soil_fert: gen_map(maps.next().ok_or(eyre!("missing next"))?)?,
-            fert_watr: gen_map(maps.next().ok_or(eyre!("missing next"))?)?,
-            watr_lght: gen_map(maps.next().ok_or(eyre!("missing next"))?)?,
-            lght_temp: gen_map(maps.next().ok_or(eyre!("missing next"))?)?,
-            temp_humd: gen_map(maps.next().ok_or(eyre!("missing next"))?)?,
-            humd_loct: gen_map(maps.next().ok_or(eyre!("missing next"))?)?,
-        })
+        Err(eyre!("maps from {} cycle without reaching {}", from, to))
     }
 }
 
-pub fn solve_one(input: &str) -> Result<String> {
-    let (seeds, maps) = input.split_once('\n').ok_or(eyre!("missing new line"))?;
-    let seeds = &seeds
-        .strip_prefix("seeds: ")
+fn parse_seeds(line: &str) -> Result<Vec<i64>> {
+    line.strip_prefix("seeds: ")
         .ok_or(eyre!("missing prefix"))?
         .split(' ')
-        .map(|v| Ok(Seed(v.parse::<i64>()?)))
-        .collect::<Result<Vec<Seed>>>()?;
-    let maps = &Maps::parse_input(maps.trim())?;
+        .filter(|v| !v.is_empty())
+        .map(|v| Ok(v.parse::<i64>()?))
+        .collect()
+}
 
-    Ok(seeds
+/// Parse a `destination source length` line.
+fn parse_piece(line: &str) -> Result<Piece> {
+    let l = line
+        .split(' ')
+        .filter(|v| !v.is_empty())
+        .map(|v| Ok(v.parse::<i64>()?))
+        .collect::<Result<Vec<i64>>>()?;
+    if l.len() != 3 {
+        eyre::bail!("expected destination, source and length");
+    }
+    Ok(Piece {
+        range: l[1]..l[1] + l[2],
+        offset: l[0] - l[1],
+    })
+}
+
+pub fn solve_one(input: &str) -> Result<String> {
+    let almanac = Almanac::parse(input)?;
+    timing::parsed();
+    let map = almanac.map_between("seed", "location")?;
+    Ok(almanac
+        .seeds
         .iter()
-        .map(|s| {
-            s.map(maps) // soil
-                .map(maps) // fert
-                .map(maps) // water
-                .map(maps) // light
-                .map(maps) // temp
-                .map(maps) // humd
-                .map(maps) // loc
-                .0
-        })
+        .map(|&s| map.apply(s))
         .min()
         .ok_or(eyre!("no values"))?
         .to_string())
 }
 
-// Part two - part one solution doesnt generalise nicely to part two, so not reusing
 pub fn solve_two(input: &str) -> Result<String> {
-    let (seeds, maps) = input.split_once('\n').ok_or(eyre!("missing new line"))?;
-    let seeds = &seeds
-        .strip_prefix("seeds: ")
-        .ok_or(eyre!("missing prefix"))?
-        .split(' ')
-        .map(|v| Ok(v.parse::<i64>()?))
-        .collect::<Result<Vec<i64>>>()?
-        .chunks(2)
-        .map(|c| c[0]..c[0] + c[1])
-        .collect::<Vec<Range<i64>>>();
-    let maps = &Maps::parse_input(maps.trim())?;
-
-    let soil = map_ranges(seeds, &maps.seed_soil);
-    let fert = map_ranges(&soil, &maps.soil_fert);
-    let water = map_ranges(&fert, &maps.fert_watr);
-    let light = map_ranges(&water, &maps.watr_lght);
-    let temp = map_ranges(&light, &maps.lght_temp);
-    let humd = map_ranges(&temp, &maps.temp_humd);
-    let loc = map_ranges(&humd, &maps.humd_loct);
-    Ok(loc
+    let almanac = Almanac::parse(input)?;
+    let seeds = almanac.seed_ranges()?;
+    timing::parsed();
+    let map = almanac.map_between("seed", "location")?;
+    Ok(seeds
         .iter()
-        .map(|l| l.start)
+        .flat_map(|r| map.apply_range(r))
+        .map(|r| r.start)
         .min()
         .ok_or(eyre!("missing min"))?
         .to_string())
 }
-
-fn map_ranges(from: &[Range<i64>], map: &[(Range<i64>, i64)]) -> Vec<Range<i64>> {
-    let mut orig = from.to_vec();
-    let mut mapped = vec![];
-
-    map.iter().for_each(|(r, d)| {
-        let (new_orig, mut add_mapped) = orig
-            .iter()
-            .map(|o| {
-                if o.end <= r.start || r.end <= o.start {
-                    // no overlap
-                    (vec![o.clone()], None)
-                } else if o.start >= r.start && o.end <= r.end {
-                    // o fully in r
-                    (vec![], Some(o.start + d..o.end + d))
-                } else if r.start >= o.start && r.end <= o.end {
-                    // r fully in o
-                    (
-                        vec![o.start..r.start, r.end..o.end],
-                        Some(r.start + d..o.start + d),
-                    )
-                } else if o.start < r.start {
-                    // o before r with overlap
-                    #[allow(clippy::single_range_in_vec_init)]
-                    (vec![o.start..r.start], Some(r.start + d..o.end + d))
-                } else {
-                    // o after r with overlap
-                    #[allow(clippy::single_range_in_vec_init)]
-                    (vec![r.end..o.end], Some(o.start + d..r.end + d))
-                }
-            })
-            .fold((vec![], vec![]), |mut out, (mut o, m)| {
-                out.0.append(&mut o);
-                if let Some(m) = m {
-                    out.1.push(m);
-                }
-                out
-            });
-        orig = new_orig;
-        mapped.append(&mut add_mapped);
-    });
-
-    mapped.append(&mut orig);
-    mapped
-}
@@ -0,0 +1,115 @@
+use day5::{Almanac, PiecewiseMap};
+
+const EXAMPLE: &str = "seeds: 79 14 55 13
+
+seed-to-soil map:
+50 98 2
+52 50 48
+
+soil-to-fertilizer map:
+0 15 37
+37 52 2
+39 0 15
+
+fertilizer-to-water map:
+49 53 8
+0 11 42
+42 0 7
+57 7 4
+
+water-to-light map:
+88 18 7
+18 25 70
+
+light-to-temperature map:
+45 77 23
+81 45 19
+68 64 13
+
+temperature-to-humidity map:
+0 69 1
+1 0 69
+
+humidity-to-location map:
+60 56 37
+56 93 4";
+
+/// Apply each map in turn, as the puzzle describes.
+fn apply_each(almanac: &Almanac, value: i64) -> i64 {
+    almanac.maps.iter().fold(value, |v, m| m.map.apply(v))
+}
+
+#[test]
+fn composed_map_matches_each_map_in_turn() {
+    let almanac = Almanac::parse(EXAMPLE).unwrap();
+    let map = almanac.map_between("seed", "location").unwrap();
+    assert_eq!(
+        map,
+        almanac
+            .maps
+            .iter()
+            .fold(PiecewiseMap::default(), |m, next| m.then(&next.map))
+    );
+    for seed in -10..150 {
+        assert_eq!(map.apply(seed), apply_each(&almanac, seed), "seed {seed}");
+    }
+    assert_eq!(
+        almanac
+            .seeds
+            .iter()
+            .map(|&s| map.apply(s))
+            .collect::<Vec<i64>>(),
+        [82, 43, 86, 35]
+    );
+
+    // every seed in the ranges maps into the ranges they are mapped to
+    for range in almanac.seed_ranges().unwrap() {
+        let mapped = map.apply_range(&range);
+        assert_eq!(
+            mapped.iter().map(|r| r.end - r.start).sum::<i64>(),
+            range.end - range.start
+        );
+        for seed in range {
+            let location = apply_each(&almanac, seed);
+            assert!(mapped.iter().any(|r| r.contains(&location)), "seed {seed}");
+        }
+    }
+
+    assert_eq!(
+        almanac.map_between("seed", "seed").unwrap(),
+        PiecewiseMap::default()
+    );
+    assert!(almanac.map_between("seed", "colour").is_err());
+    assert_eq!(day5::solve_one(EXAMPLE).unwrap(), "35");
+    assert_eq!(day5::solve_two(EXAMPLE).unwrap(), "46");
+}
+
+#[test]
+fn inverse() {
+    let almanac = Almanac::parse(EXAMPLE).unwrap();
+    let map = almanac.map_between("seed", "location").unwrap();
+    assert_eq!(map.inverse(35), [13]);
+    assert_eq!(map.inverse(46), [82]);
+    for location in -10..150 {
+        let seeds = map.inverse(location);
+        assert_eq!(seeds.len(), 1, "location {location}");
+        assert_eq!(apply_each(&almanac, seeds[0]), location);
+    }
+
+    // humidities from 100 also map onto the first locations, so those have two seeds each
+    let almanac = Almanac::parse(&format!("{}\n0 100 10", EXAMPLE)).unwrap();
+    let map = almanac.map_between("seed", "location").unwrap();
+    assert_eq!(map.inverse(5), [30, 105]);
+    assert_eq!(map.inverse(0), [70, 100]);
+    let mut seeds = map
+        .preimage(&(0..10))
+        .into_iter()
+        .flatten()
+        .collect::<Vec<i64>>();
+    seeds.sort();
+    let expected = (-10..150)
+        .filter(|&s| (0..10).contains(&apply_each(&almanac, s)))
+        .collect::<Vec<i64>>();
+    assert_eq!(seeds, expected);
+    assert_eq!(seeds.len(), 20);
+}
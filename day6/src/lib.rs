@@ -1,28 +1,38 @@
 use eyre::{eyre, Result};
+use utils::input::InputContext;
+use utils::timing;
 
-fn parse_input(input: &str) -> Result<Vec<(i64, i64)>> {
-    let (t, d) = input.split_once('\n').ok_or(eyre!("missing new line"))?;
-    let t = t
-        .strip_prefix("Time: ")
-        .ok_or(eyre!("missing time prefix"))?
-        .trim()
-        .split(char::is_whitespace)
-        .filter(|s| !s.is_empty())
-        .map(|v| Ok(v.parse::<i64>()?))
-        .collect::<Result<Vec<i64>>>()?;
-    let d = d
-        .strip_prefix("Distance: ")
-        .ok_or(eyre!("missing distance prefix"))?
-        .trim()
+fn parse_line(line: &str, prefix: &str) -> Result<Vec<i64>> {
+    line.strip_prefix(prefix)
+        .ok_or(eyre!("missing '{}' prefix", prefix))?
         .split(char::is_whitespace)
         .filter(|s| !s.is_empty())
         .map(|v| Ok(v.parse::<i64>()?))
-        .collect::<Result<Vec<i64>>>()?;
-    Ok(t.into_iter().zip(d).collect::<Vec<(i64, i64)>>())
+        .collect()
+}
+
+fn parse_input(input: &str) -> Result<Vec<(i64, i64)>> {
+    let (t, d) = input.split_once('\n').ok_or(eyre!("missing new line"))?;
+    let d = d.trim_end();
+    let times = parse_line(t, "Time:").line_context(0, t)?;
+    let distances = parse_line(d, "Distance:").line_context(1, d)?;
+    if times.len() != distances.len() {
+        return Err(eyre!(
+            "expected {} distances, one for each time",
+            times.len()
+        ))
+        .line_context(1, d);
+    }
+    Ok(times
+        .into_iter()
+        .zip(distances)
+        .collect::<Vec<(i64, i64)>>())
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
-    solve(&parse_input(input)?)
+    let races = parse_input(input)?;
+    timing::parsed();
+    solve(&races)
 }
 
 fn solve(time_dist: &[(i64, i64)]) -> Result<String> {
@@ -42,7 +52,9 @@ fn solve(time_dist: &[(i64, i64)]) -> Result<String> {
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    let (t, d) = parse_input(input)?.iter().fold((0, 0), |mut out, part| {
+    let races = parse_input(input)?;
+    timing::parsed();
+    let (t, d) = races.iter().fold((0, 0), |mut out, part| {
         let mut t = part.0;
         while t > 0 {
             out.0 *= 10;
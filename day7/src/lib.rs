@@ -1,5 +1,7 @@
 use eyre::{eyre, Result};
 use std::collections::HashMap;
+use utils::input::InputContext;
+use utils::{explain, timing};
 
 #[derive(Clone, Debug, PartialEq, PartialOrd, Ord, Eq)]
 enum Hand {
@@ -97,6 +99,40 @@ impl Hand {
     }
 }
 
+/// Card label for a card value, jokers have value 0.
+fn card_label(c: i64) -> char {
+    match c {
+        14 => 'A',
+        13 => 'K',
+        12 => 'Q',
+        11 | 0 => 'J',
+        10 => 'T',
+        c => char::from_digit(c as u32, 10).unwrap_or('?'),
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn parse_hand(line: &str, with_joker: bool) -> Result<(Hand, (i64, i64, i64, i64, i64), i64)> {
+    let (cards, bid) = line.trim().split_once(' ').ok_or(eyre!("missing space"))?;
+    let cards = cards
+        .chars()
+        .map(|c| match c {
+            'A' => Ok(14),
+            'K' => Ok(13),
+            'Q' => Ok(12),
+            'J' => Ok(if with_joker { 0 } else { 11 }),
+            'T' => Ok(10),
+            c if c != '1' && c != '0' => Ok(c.to_string().parse::<i64>()?),
+            _ => eyre::bail!("unknown card value: {}", c),
+        })
+        .collect::<Result<Vec<i64>>>()?;
+    Ok((
+        Hand::from_cards(&cards, with_joker)?,
+        (cards[0], cards[1], cards[2], cards[3], cards[4]),
+        bid.parse::<i64>()?,
+    ))
+}
+
 #[allow(clippy::type_complexity)]
 fn parse_input(
     input: &str,
@@ -104,26 +140,8 @@ fn parse_input(
 ) -> Result<Vec<(Hand, (i64, i64, i64, i64, i64), i64)>> {
     input
         .lines()
-        .map(|l| {
-            let (cards, bid) = l.trim().split_once(' ').ok_or(eyre!("missing space"))?;
-            let cards = cards
-                .chars()
-                .map(|c| match c {
-                    'A' => Ok(14),
-                    'K' => Ok(13),
-                    'Q' => Ok(12),
-                    'J' => Ok(if with_joker { 0 } else { 11 }),
-                    'T' => Ok(10),
-                    c if c != '1' && c != '0' => Ok(c.to_string().parse::<i64>()?),
-                    _ => eyre::bail!("unknown card value: {}", c),
-                })
-                .collect::<Result<Vec<i64>>>()?;
-            Ok((
-                Hand::from_cards(&cards, with_joker)?,
-                (cards[0], cards[1], cards[2], cards[3], cards[4]),
-                bid.parse::<i64>()?,
-            ))
-        })
+        .enumerate()
+        .map(|(i, l)| parse_hand(l, with_joker).line_context(i, l))
         .collect()
 }
 
@@ -144,6 +162,16 @@ fn get_total_winnings(mut hands: Vec<(Hand, (i64, i64, i64, i64, i64), i64)>) ->
             l.1 .4.cmp(&r.1 .4)
         }
     });
+    explain::section("ranked hands");
+    for (i, (hand, cards, bid)) in hands.iter().enumerate() {
+        explain::line(|| {
+            let cards = [cards.0, cards.1, cards.2, cards.3, cards.4]
+                .into_iter()
+                .map(card_label)
+                .collect::<String>();
+            format!("{}: {} {:?}, bid {}", i + 1, cards, hand, bid)
+        });
+    }
     Ok(hands
         .iter()
         .enumerate()
@@ -156,9 +184,13 @@ fn get_total_winnings(mut hands: Vec<(Hand, (i64, i64, i64, i64, i64), i64)>) ->
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
-    get_total_winnings(parse_input(input, false)?)
+    let hands = parse_input(input, false)?;
+    timing::parsed();
+    get_total_winnings(hands)
 }
 
 pub fn solve_two(input: &str) -> Result<String> {
-    get_total_winnings(parse_input(input, true)?)
+    let hands = parse_input(input, true)?;
+    timing::parsed();
+    get_total_winnings(hands)
 }
@@ -1,5 +1,8 @@
-use eyre::{eyre, Result};
+use eyre::{bail, eyre, Result};
+use num_integer::Integer;
 use std::collections::HashMap;
+use utils::input::InputContext;
+use utils::timing;
 
 #[derive(Clone, Debug)]
 enum LeftRight {
@@ -7,29 +10,37 @@ enum LeftRight {
     Right,
 }
 
+fn parse_node(line: &str) -> Result<(&str, (&str, &str))> {
+    let (from, to) = line.split_once(" = ").ok_or(eyre!("missing ="))?;
+    let to_lr = to
+        .strip_prefix('(')
+        .ok_or(eyre!("missing ("))?
+        .strip_suffix(')')
+        .ok_or(eyre!("missing )"))?
+        .split_once(", ")
+        .ok_or(eyre!("missing ,"))?;
+    Ok((from, to_lr))
+}
+
 #[allow(clippy::type_complexity)]
 fn parse_input(input: &str) -> Result<(Vec<LeftRight>, HashMap<&str, (&str, &str)>)> {
     let (lr, map) = input.split_once("\n\n").ok_or(eyre!("missing new lines"))?;
     Ok((
         lr.chars()
-            .map(|c| match c {
-                'L' => Ok(LeftRight::Left),
-                'R' => Ok(LeftRight::Right),
-                _ => eyre::bail!("unexpected char: {}", c),
+            .enumerate()
+            .map(|(j, c)| {
+                match c {
+                    'L' => Ok(LeftRight::Left),
+                    'R' => Ok(LeftRight::Right),
+                    _ => Err(eyre!("unexpected char: {}", c)),
+                }
+                .column_context(0, j, lr)
             })
             .collect::<Result<Vec<LeftRight>>>()?,
+        // map starts after the instructions and a blank line
         map.lines()
-            .map(|l| {
-                let (from, to) = l.split_once(" = ").ok_or(eyre!("missing ="))?;
-                let to_lr = to
-                    .strip_prefix('(')
-                    .ok_or(eyre!("missing ("))?
-                    .strip_suffix(')')
-                    .ok_or(eyre!("missing )"))?
-                    .split_once(", ")
-                    .ok_or(eyre!("missing ,"))?;
-                Ok((from, to_lr))
-            })
+            .enumerate()
+            .map(|(i, l)| parse_node(l).line_context(i + 2, l))
             .collect::<Result<HashMap<&str, (&str, &str)>>>()?,
     ))
 }
@@ -77,20 +88,134 @@ fn num_of_steps(
 
 pub fn solve_one(input: &str) -> Result<String> {
     let (lr, map) = parse_input(input)?;
+    timing::parsed();
     Ok(num_of_steps(&lr, &map, "AAA", |e| e == "ZZZ")?.to_string())
 }
 
+/// Times at which a ghost is stood on an end node. `pre` are the hits before
+/// the walk enters its cycle, every time in `cycle` repeats every `period`
+/// steps for as long as the ghost keeps walking.
+#[derive(Debug)]
+struct EndHits {
+    pre: Vec<u64>,
+    cycle_start: u64,
+    period: u64,
+    cycle: Vec<u64>,
+}
+impl EndHits {
+    fn is_hit(&self, t: u64) -> bool {
+        if t < self.cycle_start {
+            self.pre.contains(&t)
+        } else {
+            let offset = (t - self.cycle_start) % self.period;
+            self.cycle.contains(&(self.cycle_start + offset))
+        }
+    }
+}
+
+fn end_hits(
+    lr: &[LeftRight],
+    map: &HashMap<&str, (&str, &str)>,
+    start: &str,
+    end: impl Fn(&str) -> bool,
+) -> Result<EndHits> {
+    if lr.is_empty() {
+        bail!("no left/right instructions");
+    }
+    // walk until a (node, instruction index) state repeats, that gives the cycle
+    let mut seen = HashMap::new();
+    let mut hits = vec![];
+    let mut cur = start;
+    let mut t = 0;
+    let cycle_start = loop {
+        let i = t as usize % lr.len();
+        if let Some(&first) = seen.get(&(cur, i)) {
+            break first;
+        }
+        seen.insert((cur, i), t);
+        if end(cur) {
+            hits.push(t);
+        }
+        let to = map.get(cur).ok_or(eyre!("{} missing from map", cur))?;
+        cur = match lr[i] {
+            LeftRight::Left => to.0,
+            LeftRight::Right => to.1,
+        };
+        t += 1;
+    };
+    let (pre, cycle) = hits.into_iter().partition(|&h| h < cycle_start);
+    Ok(EndHits {
+        pre,
+        cycle_start,
+        period: t - cycle_start,
+        cycle,
+    })
+}
+
+/// Generalised chinese remainder theorem, combines `x = a mod m` and `x = b mod n`
+/// into a single `x = c mod lcm(m, n)`, or `None` if they never coincide.
+fn crt((a, m): (i128, i128), (b, n): (i128, i128)) -> Option<(i128, i128)> {
+    let egcd = m.extended_gcd(&n);
+    if (b - a) % egcd.gcd != 0 {
+        return None;
+    }
+    let lcm = m / egcd.gcd * n;
+    let k = ((b - a) / egcd.gcd * egcd.x) % (n / egcd.gcd);
+    Some(((a + m * k).rem_euclid(lcm), lcm))
+}
+
+/// First step at which every ghost is on an end node at the same time.
+fn first_common_hit(ghosts: &[EndHits]) -> Option<u64> {
+    // hits before a ghost cycles are finite, so just check them directly
+    let pre = ghosts
+        .iter()
+        .flat_map(|g| g.pre.iter())
+        .filter(|&&t| ghosts.iter().all(|g| g.is_hit(t)))
+        .min()
+        .copied();
+
+    // remaining hits must be past every ghost's cycle start, and are the
+    // combination of each ghost's cycle offsets
+    let min_t = ghosts.iter().map(|g| g.cycle_start).max().unwrap_or(0) as i128;
+    let residues = ghosts.iter().fold(vec![(0_i128, 1_i128)], |acc, g| {
+        acc.into_iter()
+            .flat_map(|r| {
+                g.cycle
+                    .iter()
+                    .filter_map(move |&c| crt(r, (c as i128, g.period as i128)))
+            })
+            .collect()
+    });
+    let cycle = residues
+        .into_iter()
+        .map(|(r, m)| {
+            if r >= min_t {
+                r
+            } else {
+                r + (min_t - r + m - 1) / m * m
+            }
+        })
+        .min()
+        .map(|t| t as u64);
+
+    match (pre, cycle) {
+        (Some(p), Some(c)) => Some(p.min(c)),
+        (p, c) => p.or(c),
+    }
+}
+
 pub fn solve_two(input: &str) -> Result<String> {
     let (lr, map) = parse_input(input)?;
-    let steps = map
-        .clone()
-        .into_keys()
+    timing::parsed();
+    let ghosts = map
+        .keys()
         .filter(|k| k.ends_with('A'))
-        .map(|s| num_of_steps(&lr, &map, s, |e| e.ends_with('Z')))
-        .collect::<Result<Vec<usize>>>()?;
-    Ok(steps
-        .into_iter()
-        .map(|i| i as u64)
-        .fold(1, num_integer::lcm)
+        .map(|&s| end_hits(&lr, &map, s, |e| e.ends_with('Z')))
+        .collect::<Result<Vec<EndHits>>>()?;
+    if ghosts.is_empty() {
+        bail!("no start nodes ending in A");
+    }
+    Ok(first_common_hit(&ghosts)
+        .ok_or(eyre!("ghosts never all reach end nodes together"))?
         .to_string())
 }
@@ -0,0 +1,86 @@
+use std::collections::HashMap;
+
+/// Step every ghost together until they are all on end nodes.
+fn brute_force(input: &str, limit: usize) -> Option<usize> {
+    let (lr, nodes) = input.split_once("\n\n").unwrap();
+    let map = nodes
+        .lines()
+        .map(|l| (&l[0..3], (&l[7..10], &l[12..15])))
+        .collect::<HashMap<&str, (&str, &str)>>();
+    let mut ghosts = map
+        .keys()
+        .filter(|k| k.ends_with('A'))
+        .copied()
+        .collect::<Vec<&str>>();
+    for (t, c) in lr.chars().cycle().enumerate().take(limit) {
+        if ghosts.iter().all(|g| g.ends_with('Z')) {
+            return Some(t);
+        }
+        for g in ghosts.iter_mut() {
+            *g = if c == 'L' { map[g].0 } else { map[g].1 };
+        }
+    }
+    None
+}
+
+#[test]
+fn several_end_hits_per_cycle() {
+    // ghost 1 hits at 2 and 4 in a cycle of 5, ghost 2 at 3 in a cycle of 4 and ghost 3 on
+    // every odd step from 3, none of them at the cycle length
+    let input = "LR
+
+1AA = (1BB, 1BB)
+1BB = (1ZZ, 1ZZ)
+1ZZ = (1CC, 1CC)
+1CC = (1DZ, 1DZ)
+1DZ = (1EE, 1EE)
+1EE = (1BB, 1BB)
+2AA = (2BB, 2BB)
+2BB = (2CC, 2CC)
+2CC = (2ZZ, 2ZZ)
+2ZZ = (2DD, 2DD)
+2DD = (2BB, 2BB)
+3AA = (3BB, 3BB)
+3BB = (3CC, 3CC)
+3CC = (3ZZ, 3ZZ)
+3ZZ = (3CC, 3CC)";
+    assert_eq!(brute_force(input, 1000), Some(7));
+    assert_eq!(day8::solve_two(input).unwrap(), "7");
+}
+
+#[test]
+fn end_hit_before_cycle() {
+    // ghost 1 only hits before it settles into a loop
+    let input = "L
+
+1AA = (1ZZ, 1ZZ)
+1ZZ = (1BB, 1BB)
+1BB = (1BB, 1BB)
+2AA = (2ZZ, 2ZZ)
+2ZZ = (2CC, 2CC)
+2CC = (2ZZ, 2ZZ)";
+    assert_eq!(brute_force(input, 1000), Some(1));
+    assert_eq!(day8::solve_two(input).unwrap(), "1");
+}
+
+#[test]
+fn never_together() {
+    let input = "L
+
+1AA = (1ZZ, 1ZZ)
+1ZZ = (1AA, 1AA)
+2AA = (2BB, 2BB)
+2BB = (2ZZ, 2ZZ)
+2ZZ = (2BB, 2BB)";
+    assert_eq!(brute_force(input, 1000), None);
+    assert!(day8::solve_two(input).is_err());
+}
+
+#[test]
+fn no_start_nodes() {
+    let input = "L
+
+BBB = (ZZZ, ZZZ)
+ZZZ = (BBB, BBB)";
+    assert!(day8::solve_two(input).is_err());
+}
@@ -1,18 +1,23 @@
 use eyre::{eyre, Result};
+use utils::input::InputContext;
+use utils::timing;
 
 fn parse_input(input: &str) -> Result<Vec<Vec<i64>>> {
     input
         .lines()
-        .map(|l| {
+        .enumerate()
+        .map(|(i, l)| {
             l.split(' ')
                 .map(|v| Ok(v.parse::<i64>()?))
                 .collect::<Result<Vec<i64>>>()
+                .line_context(i, l)
         })
         .collect()
 }
 
 pub fn solve_one(input: &str) -> Result<String> {
     let history = parse_input(input)?;
+    timing::parsed();
     Ok(history
         .into_iter()
         .map(|v| {
@@ -35,6 +40,7 @@ pub fn solve_one(input: &str) -> Result<String> {
 
 pub fn solve_two(input: &str) -> Result<String> {
     let history = parse_input(input)?;
+    timing::parsed();
     Ok(history
         .into_iter()
         .map(|v| {
@@ -0,0 +1,110 @@
+//! Examples from each day's puzzle description, run by `tests/parity.rs` both natively and on
+//! wasm32 to check the two builds of the day crates give the same answers.
+
+use eyre::{bail, Result};
+use std::fmt::Write;
+
+/// Example input for a day's part, `name` is the file in `inputs`.
+pub struct Example {
+    pub day: u8,
+    pub part: u8,
+    pub name: &'static str,
+    pub input: &'static str,
+}
+macro_rules! example {
+    ($day: literal, $part: literal, $name: literal) => {
+        Example {
+            day: $day,
+            part: $part,
+            name: $name,
+            input: include_str!(concat!("../inputs/", $name, ".txt")),
+        }
+    };
+}
+
+/// Days 1, 8 and 10 have a different example for part two. Day 20 part two has no example, and
+/// does not finish on the part one examples as they have no `rx` module. Day 24 part one counts
+/// crossings in the real input's test area, not the example's, and day 25 has no part two, so
+/// neither has an example answer to check.
+pub const EXAMPLES: &[Example] = &[
+    example!(1, 1, "day1"),
+    example!(1, 2, "day1_2"),
+    example!(2, 1, "day2"),
+    example!(2, 2, "day2"),
+    example!(3, 1, "day3"),
+    example!(3, 2, "day3"),
+    example!(4, 1, "day4"),
+    example!(4, 2, "day4"),
+    example!(5, 1, "day5"),
+    example!(5, 2, "day5"),
+    example!(6, 1, "day6"),
+    example!(6, 2, "day6"),
+    example!(7, 1, "day7"),
+    example!(7, 2, "day7"),
+    example!(8, 1, "day8"),
+    example!(8, 2, "day8_2"),
+    example!(9, 1, "day9"),
+    example!(9, 2, "day9"),
+    example!(10, 1, "day10"),
+    example!(10, 2, "day10_2"),
+    example!(11, 1, "day11"),
+    example!(11, 2, "day11"),
+    example!(12, 1, "day12"),
+    example!(12, 2, "day12"),
+    example!(13, 1, "day13"),
+    example!(13, 2, "day13"),
+    example!(14, 1, "day14"),
+    example!(14, 2, "day14"),
+    example!(15, 1, "day15"),
+    example!(15, 2, "day15"),
+    example!(16, 1, "day16"),
+    example!(16, 2, "day16"),
+    example!(17, 1, "day17"),
+    example!(17, 2, "day17"),
+    example!(18, 1, "day18"),
+    example!(18, 2, "day18"),
+    example!(19, 1, "day19"),
+    example!(19, 2, "day19"),
+    example!(20, 1, "day20"),
+    example!(20, 1, "day20_2"),
+    example!(21, 1, "day21"),
+    example!(21, 2, "day21"),
+    example!(22, 1, "day22"),
+    example!(22, 2, "day22"),
+    example!(23, 1, "day23"),
+    example!(23, 2, "day23"),
+    example!(24, 2, "day24"),
+    example!(25, 1, "day25"),
+];
+
+fn solve(day: u8, part: u8, input: &str) -> Result<String> {
+    macro_rules! days {
+        ($($i: literal),*) => {
+            paste::paste! {
+                match (day, part) {
+                    $(
+                        ($i, 1) => [<day $i>]::solve_one(input),
+                        ($i, 2) => [<day $i>]::solve_two(input),
+                    )*
+                    _ => bail!("no solver for day {} part {}", day, part),
+                }
+            }
+        };
+    }
+    days!(1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25)
+}
+
+/// Answer to every example, one per line. Errors are included so they are compared too.
+pub fn answers() -> String {
+    let mut answers = String::new();
+    for example in EXAMPLES {
+        let answer = solve(example.day, example.part, example.input)
+            .unwrap_or_else(|e| format!("error: {:#}", e));
+        let _ = writeln!(
+            answers,
+            "{} part {}: {}",
+            example.name, example.part, answer
+        );
+    }
+    answers
+}
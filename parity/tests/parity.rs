@@ -0,0 +1,25 @@
+#[cfg(target_arch = "wasm32")]
+use wasm_bindgen_test::wasm_bindgen_test as test;
+
+/// Golden answers from a native run, regenerated with `UPDATE_ANSWERS=1 cargo test -p parity`. CI
+/// regenerates them before the wasm run and fails if they changed, so the wasm answers are checked
+/// against the native answers of the same commit.
+const ANSWERS: &str = include_str!("../answers.txt");
+
+#[test]
+fn examples_match_native_answers() {
+    let answers = parity::answers();
+    #[cfg(not(target_arch = "wasm32"))]
+    if std::env::var_os("UPDATE_ANSWERS").is_some() {
+        std::fs::write(
+            concat!(env!("CARGO_MANIFEST_DIR"), "/answers.txt"),
+            &answers,
+        )
+        .unwrap();
+        return;
+    }
+    for (answer, expected) in answers.lines().zip(ANSWERS.lines()) {
+        assert_eq!(answer, expected);
+    }
+    assert_eq!(answers.lines().count(), ANSWERS.lines().count());
+}
@@ -1,15 +1,41 @@
 use eyre::{eyre, Result};
 use proc_macro::{TokenStream, TokenTree};
-use quote::quote;
-use syn::{parse_macro_input, parse_quote, ItemFn};
+use quote::{quote, quote_spanned};
+use syn::spanned::Spanned;
+use syn::{parse_macro_input, parse_quote, ItemFn, Type};
 
+/// Generates the cli `main` for a day from its part functions, `#[aoc(dayX, part<1|2>)]`.
+///
+/// A day with options of its own adds `args = Type` to the attribute of each part that takes
+/// them, where `Type` derives `clap::Args`. Those part functions take the options as a second
+/// argument, the others only get the input. If both parts take options they must take the same
+/// type, a different one on part two is an error at its `args`.
 #[proc_macro_attribute]
 pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
-    let (d, p) = parse_attr(attr).unwrap();
+    let (d, p, args) = parse_attr(attr).unwrap();
     let mut func = parse_macro_input!(item as ItemFn);
     func.vis = parse_quote!(pub);
     let gen = if p == 1 {
         func.sig.ident = parse_quote!(solve_one);
+        // options declared by part one, or else by part two
+        let (args_type, run_one) = match args {
+            Some(args) => (
+                quote!(#args),
+                quote! {
+                    fn run_one(input: &str, args: &#args) -> eyre::Result<String> {
+                        inner_one::solve_one(input, args)
+                    }
+                },
+            ),
+            None => (
+                quote!(PartTwoArgs),
+                quote! {
+                    fn run_one<A>(input: &str, _: &A) -> eyre::Result<String> {
+                        inner_one::solve_one(input)
+                    }
+                },
+            ),
+        };
         quote! {
 
             mod inner_one {
@@ -19,17 +45,27 @@ pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
 
                 #func
             }
+            // checked against the options of part two, if it has any
+            #[allow(dead_code)]
+            type PartOneArgs = #args_type;
+            #run_one
             fn main() -> eyre::Result<()> {
+                use eyre::WrapErr;
                 use utils::Parser;
-                let args = utils::Args::parse();
+                let args = utils::Args::<#args_type>::parse();
+                args.init_logging();
 
                 let input = args.get_input(#d)?;
 
                 if args.run_one() {
-                    println!("part one:\n{}", inner_one::solve_one(&input)?);
+                    let _span = utils::log::span(format!("day{} part one", #d));
+                    let answer = args.solve(&input, |input| run_one(input, &args.extra));
+                    println!("part one:\n{}", answer.wrap_err("part one")?);
                 }
                 if args.run_two() {
-                    println!("part two:\n{}", inner_two::solve_two(&input)?);
+                    let _span = utils::log::span(format!("day{} part two", #d));
+                    let answer = args.solve(&input, |input| run_two(input, &args.extra));
+                    println!("part two:\n{}", answer.wrap_err("part two")?);
                 }
 
                 Ok(())
@@ -37,6 +73,34 @@ pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
         }
     } else {
         func.sig.ident = parse_quote!(solve_two);
+        let (args_type, run_two) = match args {
+            Some(args) => {
+                // spanned so that options differing from part one's are reported at the attribute
+                let same_args = quote_spanned! {args.span()=>
+                    const _: () = {
+                        fn same_args<A: utils::SameArgs<PartOneArgs>>() {}
+                        let _ = same_args::<#args>;
+                    };
+                };
+                (
+                    quote!(#args),
+                    quote! {
+                        #same_args
+                        fn run_two(input: &str, args: &#args) -> eyre::Result<String> {
+                            inner_two::solve_two(input, args)
+                        }
+                    },
+                )
+            }
+            None => (
+                quote!(utils::NoArgs),
+                quote! {
+                    fn run_two<A>(input: &str, _: &A) -> eyre::Result<String> {
+                        inner_two::solve_two(input)
+                    }
+                },
+            ),
+        };
         quote! {
             mod inner_two {
                 use crate::*;
@@ -45,13 +109,26 @@ pub fn aoc(attr: TokenStream, item: TokenStream) -> TokenStream {
 
                 #func
             }
+            // only used by main when part one has no options of its own
+            #[allow(dead_code)]
+            type PartTwoArgs = #args_type;
+            #run_two
         }
     };
     gen.into()
 }
 
-fn parse_attr(attr: TokenStream) -> Result<(i32, i32)> {
-    let mut i = attr.into_iter().filter_map(|t| {
+fn parse_attr(attr: TokenStream) -> Result<(i32, i32, Option<Type>)> {
+    // split on commas, as the args type may be a path
+    let mut groups = vec![vec![]];
+    for t in attr {
+        match &t {
+            TokenTree::Punct(p) if p.as_char() == ',' => groups.push(vec![]),
+            _ => groups.last_mut().ok_or(eyre!("missing group"))?.push(t),
+        }
+    }
+    let mut groups = groups.into_iter();
+    let mut i = groups.by_ref().take(2).flatten().filter_map(|t| {
         if let TokenTree::Ident(_) = t {
             Some(t.to_string())
         } else {
@@ -96,8 +173,29 @@ fn parse_attr(attr: TokenStream) -> Result<(i32, i32)> {
                 })
         })?;
     if let Some(n) = i.next() {
-        Err(eyre!("unexpected attr - {}", n))
+        return Err(eyre!("unexpected attr - {}", n));
+    }
+    let args = groups
+        .next()
+        .map(|group| {
+            let mut tokens = group.into_iter();
+            match (tokens.next(), tokens.next()) {
+                (Some(TokenTree::Ident(name)), Some(TokenTree::Punct(eq)))
+                    if name.to_string() == "args" && eq.as_char() == '=' =>
+                {
+                    syn::parse::<Type>(tokens.collect())
+                        .map_err(|e| eyre!("expecting 'args = Type', could not parse type - {}", e))
+                }
+                _ => Err(eyre!("expecting 'args = Type' third attribute")),
+            }
+        })
+        .transpose()?;
+    if let Some(g) = groups.next() {
+        Err(eyre!(
+            "unexpected attr - {}",
+            g.into_iter().collect::<TokenStream>()
+        ))
     } else {
-        Ok((d, p))
+        Ok((d, p, args))
     }
 }
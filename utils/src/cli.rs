@@ -0,0 +1,130 @@
+use crate::log::{self, Level};
+use crate::{explain, timing};
+use clap::{ArgAction, Args, Parser};
+use eyre::{bail, eyre, Result};
+use reqwest::blocking::Client;
+use reqwest::header::COOKIE;
+use std::env;
+use std::fs;
+
+/// Options for days without options of their own.
+#[derive(Debug, Default, Args)]
+pub struct NoArgs {}
+
+// only implemented for a type with itself, the `aoc` attribute uses it to check both parts
+// take the same options
+#[doc(hidden)]
+#[diagnostic::on_unimplemented(
+    message = "part two takes options `{Self}` but part one takes `{A}`",
+    label = "both parts must take the same options type"
+)]
+pub trait SameArgs<A> {}
+impl<A> SameArgs<A> for A {}
+
+// options common to every day, with the day's own options `A` flattened in
+#[derive(Debug, Parser)]
+#[command(long_about = None)]
+pub struct UtilArgs<A: Args = NoArgs> {
+    /// input file, AOC_SESSION env must be set if not specified
+    #[arg(short, long)]
+    input: Option<String>,
+    /// run part one, will run both parts if --one and --two not specified
+    #[arg(short, long)]
+    one: bool,
+    /// run part two, will run both parts if --one and --two not specified
+    #[arg(short, long)]
+    two: bool,
+    /// time each part over this many runs, printing the min, median and max run times
+    #[arg(short, long)]
+    runs: Option<u32>,
+    /// print a trace of the intermediate values of each part after its answer
+    #[arg(short, long)]
+    explain: bool,
+    /// log solver diagnostics to stderr, -v for info and -vv for debug messages
+    #[arg(short, long, action = ArgAction::Count)]
+    verbose: u8,
+    #[command(flatten)]
+    pub extra: A,
+}
+impl<A: Args> UtilArgs<A> {
+    pub fn get_input(&self, day: i32) -> Result<String> {
+        if let Some(file) = &self.input {
+            log::info(|| format!("reading input from {}", file));
+            Ok(fs::read_to_string(file)?)
+        } else if let Some(session) = env::var_os("AOC_SESSION") {
+            log::info(|| format!("downloading input for day {}", day));
+            let client = Client::new();
+            Ok(client
+                .get(format!("https://adventofcode.com/2023/day/{}/input", day))
+                .header(
+                    COOKIE,
+                    format!(
+                        "session={}",
+                        session
+                            .to_str()
+                            .ok_or_else(|| eyre!("cannot convert env to str"))?
+                    ),
+                )
+                .send()?
+                .text()?)
+        } else {
+            bail!("no input file provided or AOC_SESSION set");
+        }
+    }
+
+    /// Log the solvers' diagnostics at the level picked by `-v`, messages go to stderr so they are
+    /// kept apart from the answers.
+    pub fn init_logging(&self) {
+        let level = match self.verbose {
+            0 => return,
+            1 => Level::Info,
+            _ => Level::Debug,
+        };
+        log::set_logger(level, |record| eprintln!("{}", record));
+    }
+
+    pub fn run_one(&self) -> bool {
+        self.one || !self.two
+    }
+
+    pub fn run_two(&self) -> bool {
+        self.two || !self.one
+    }
+
+    /// Run the solver, with its run times after the answer if `--runs` is set and the explanation
+    /// of an extra untimed run if `--explain` is set.
+    pub fn solve(&self, input: &str, solve: impl Fn(&str) -> Result<String>) -> Result<String> {
+        // explaining slows the solve, so the timed runs are never explained
+        let timed = match self.runs {
+            Some(runs) => Some(timing::time_runs(timing::native_clock, runs, || {
+                solve(input)
+            })?),
+            None => None,
+        };
+        let explained = if self.explain {
+            let (answer, explanation) = explain::collect(|| solve(input));
+            Some((answer?, explanation))
+        } else {
+            None
+        };
+        let mut output = match (timed, &explained) {
+            (Some((answer, times)), _) => format!("{}\n{}", answer, timing::Summary(&times)),
+            (None, Some((answer, _))) => answer.clone(),
+            (None, None) => solve(input)?,
+        };
+        match explained {
+            Some((_, explanation)) if explanation.0.is_empty() => {
+                output = format!("{}\nno explanation for this day", output);
+            }
+            Some((_, explanation)) => {
+                output = format!(
+                    "{}\nexplanation:\n{}",
+                    output,
+                    explanation.to_string().trim_end()
+                );
+            }
+            None => {}
+        }
+        Ok(output)
+    }
+}
@@ -0,0 +1,78 @@
+//! Explaining a solve with a trace of its intermediate values, for finding where a wrong answer
+//! comes from.
+//!
+//! Solvers call [`section`] and [`line`] as they work, which do nothing unless the solve is run by
+//! [`collect`], e.g. for the cli `--explain` option or the web app's `Explain` checkbox. Lines are
+//! only formatted while collecting, so explaining costs nothing in a normal solve.
+
+use std::cell::RefCell;
+use std::fmt;
+
+/// Titled group of lines in an explanation.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Section {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+
+/// Trace of a solve, in the order the solver reported it.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Explanation(pub Vec<Section>);
+impl fmt::Display for Explanation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for section in &self.0 {
+            writeln!(f, "{}:", section.title)?;
+            for line in &section.lines {
+                writeln!(f, "  {}", line)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+thread_local! {
+    static TRACE: RefCell<Option<Vec<Section>>> = const { RefCell::new(None) };
+}
+
+/// Whether the solve is being explained, for solvers to skip work only needed by an explanation.
+pub fn explaining() -> bool {
+    TRACE.with(|t| t.borrow().is_some())
+}
+
+/// Start a new section, the following lines are added to it.
+pub fn section(title: &str) {
+    TRACE.with(|t| {
+        if let Some(trace) = t.borrow_mut().as_mut() {
+            trace.push(Section {
+                title: title.to_string(),
+                lines: Vec::new(),
+            });
+        }
+    });
+}
+
+/// Add a line to the current section, `line` is only called when explaining.
+pub fn line(line: impl FnOnce() -> String) {
+    if !explaining() {
+        return;
+    }
+    let line = line();
+    TRACE.with(|t| {
+        if let Some(trace) = t.borrow_mut().as_mut() {
+            if trace.is_empty() {
+                trace.push(Section::default());
+            }
+            if let Some(section) = trace.last_mut() {
+                section.lines.push(line);
+            }
+        }
+    });
+}
+
+/// Run `solve` collecting the explanation it reports.
+pub fn collect<T>(solve: impl FnOnce() -> T) -> (T, Explanation) {
+    let outer = TRACE.with(|t| t.replace(Some(Vec::new())));
+    let result = solve();
+    let trace = TRACE.with(|t| t.replace(outer));
+    (result, Explanation(trace.unwrap_or_default()))
+}
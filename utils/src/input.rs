@@ -0,0 +1,59 @@
+//! Locating parse errors in the puzzle input.
+//!
+//! Parsers add an [`InputLocation`] as context to their errors with [`InputContext`], so the
+//! failing line is shown in the error chain and can be found again with [`location`], e.g. to
+//! highlight it in the wasm-runner.
+
+use eyre::{Report, Result};
+use std::fmt;
+
+/// Line, and column if known, of the input an error happened at, both numbered from 1.
+#[derive(Clone, Debug, PartialEq)]
+pub struct InputLocation {
+    pub line: usize,
+    pub column: Option<usize>,
+    /// text of the line
+    pub text: String,
+}
+
+impl fmt::Display for InputLocation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.column {
+            Some(column) => write!(f, "line {}, column {}: {:?}", self.line, column, self.text),
+            None => write!(f, "line {}: {:?}", self.line, self.text),
+        }
+    }
+}
+
+/// Add the input location to an error. Indexes are numbered from 0, as given by `enumerate`.
+pub trait InputContext<T> {
+    fn line_context(self, index: usize, line: &str) -> Result<T>;
+    fn column_context(self, index: usize, column: usize, line: &str) -> Result<T>;
+}
+
+impl<T> InputContext<T> for Result<T> {
+    fn line_context(self, index: usize, line: &str) -> Result<T> {
+        self.map_err(|e| {
+            e.wrap_err(InputLocation {
+                line: index + 1,
+                column: None,
+                text: line.to_string(),
+            })
+        })
+    }
+
+    fn column_context(self, index: usize, column: usize, line: &str) -> Result<T> {
+        self.map_err(|e| {
+            e.wrap_err(InputLocation {
+                line: index + 1,
+                column: Some(column + 1),
+                text: line.to_string(),
+            })
+        })
+    }
+}
+
+/// Outermost input location added to the error, if any.
+pub fn location(report: &Report) -> Option<&InputLocation> {
+    report.downcast_ref::<InputLocation>()
+}
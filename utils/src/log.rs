@@ -0,0 +1,113 @@
+//! Leveled diagnostics for solvers, in place of `println!` and `dbg!` which mix with the answers.
+//!
+//! Solvers call [`info`] and [`debug`] with a closure formatting the message, which is only called
+//! if a logger is set for that level, e.g. by the cli from `-v`/`-vv` or by the wasm-runner worker
+//! which forwards messages to the browser console. Messages are tagged with the open [`span`]s,
+//! such as the day and part being solved.
+
+use std::cell::RefCell;
+use std::fmt;
+
+/// Level of a message, a logger set for a level gets the messages at that level and below.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub enum Level {
+    Info,
+    Debug,
+}
+impl fmt::Display for Level {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Info => write!(f, "INFO"),
+            Self::Debug => write!(f, "DEBUG"),
+        }
+    }
+}
+
+/// Message passed to the logger.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Record<'a> {
+    pub level: Level,
+    /// open spans, outermost first
+    pub spans: &'a [String],
+    pub message: String,
+}
+impl fmt::Display for Record<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.spans.is_empty() {
+            write!(f, "{}: {}", self.level, self.message)
+        } else {
+            write!(
+                f,
+                "{} {}: {}",
+                self.level,
+                self.spans.join(": "),
+                self.message
+            )
+        }
+    }
+}
+
+type Logger = Box<dyn Fn(&Record)>;
+
+thread_local! {
+    static LOGGER: RefCell<Option<(Level, Logger)>> = RefCell::new(None);
+    static SPANS: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Set the logger that is called with each message at `max` level and below.
+pub fn set_logger(max: Level, logger: impl Fn(&Record) + 'static) {
+    LOGGER.with(|l| *l.borrow_mut() = Some((max, Box::new(logger))));
+}
+
+pub fn clear_logger() {
+    LOGGER.with(|l| *l.borrow_mut() = None);
+}
+
+/// Whether messages at `level` are logged, for solvers to skip work only needed by a message.
+pub fn enabled(level: Level) -> bool {
+    LOGGER.with(|l| l.borrow().as_ref().is_some_and(|(max, _)| level <= *max))
+}
+
+/// Log a message shown with `-v`, `message` is only called if it is logged.
+pub fn info(message: impl FnOnce() -> String) {
+    log(Level::Info, message);
+}
+
+/// Log a message shown with `-vv`, `message` is only called if it is logged.
+pub fn debug(message: impl FnOnce() -> String) {
+    log(Level::Debug, message);
+}
+
+fn log(level: Level, message: impl FnOnce() -> String) {
+    if !enabled(level) {
+        return;
+    }
+    let message = message();
+    SPANS.with(|spans| {
+        LOGGER.with(|l| {
+            if let Some((_, logger)) = l.borrow().as_ref() {
+                logger(&Record {
+                    level,
+                    spans: &spans.borrow(),
+                    message,
+                });
+            }
+        })
+    });
+}
+
+/// Open span, closed when dropped.
+#[must_use = "the span is closed when dropped"]
+pub struct Span(());
+impl Drop for Span {
+    fn drop(&mut self) {
+        SPANS.with(|spans| spans.borrow_mut().pop());
+    }
+}
+
+/// Open a span, the messages logged until it is dropped are tagged with its name.
+pub fn span(name: impl Into<String>) -> Span {
+    SPANS.with(|spans| spans.borrow_mut().push(name.into()));
+    Span(())
+}
@@ -0,0 +1,51 @@
+//! Progress reporting for long running solvers.
+//!
+//! Solvers call [`report`] as they work, which does nothing unless a reporter has been set, e.g.
+//! by the wasm-runner worker to forward progress to the web page.
+
+use std::cell::RefCell;
+
+/// How far through a solve is, `total` is `None` when the amount of work is not known upfront.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Progress {
+    pub done: u64,
+    pub total: Option<u64>,
+    pub value: Option<String>,
+}
+
+type Reporter = Box<dyn Fn(&Progress)>;
+
+thread_local! {
+    static REPORTER: RefCell<Option<Reporter>> = RefCell::new(None);
+}
+
+/// Set the reporter that is called with each solver progress update.
+pub fn set_reporter(reporter: impl Fn(&Progress) + 'static) {
+    REPORTER.with(|r| *r.borrow_mut() = Some(Box::new(reporter)));
+}
+
+pub fn clear_reporter() {
+    REPORTER.with(|r| *r.borrow_mut() = None);
+}
+
+/// Report progress of `done` out of `total`.
+pub fn report(done: u64, total: Option<u64>) {
+    report_progress(done, total, || None);
+}
+
+/// Report progress with an intermediate value, `value` is only called if there is a reporter.
+pub fn report_value(done: u64, total: Option<u64>, value: impl FnOnce() -> String) {
+    report_progress(done, total, || Some(value()));
+}
+
+fn report_progress(done: u64, total: Option<u64>, value: impl FnOnce() -> Option<String>) {
+    REPORTER.with(|r| {
+        if let Some(reporter) = r.borrow().as_ref() {
+            reporter(&Progress {
+                done,
+                total,
+                value: value(),
+            });
+        }
+    });
+}
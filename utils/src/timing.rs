@@ -0,0 +1,138 @@
+//! Timing solvers, with the time spent parsing the input split from the rest of the solve.
+//!
+//! Solvers call [`parsed`] once their input is parsed, which does nothing unless the solver is
+//! being run by [`time_runs`]. The clock is passed in, as `std::time::Instant` is not available in
+//! wasm where the worker uses `performance.now()`.
+
+use eyre::Result;
+use std::cell::Cell;
+use std::fmt;
+
+/// Clock returning the time in milliseconds from some fixed point.
+pub type Clock = fn() -> f64;
+
+thread_local! {
+    static CLOCK: Cell<Option<Clock>> = const { Cell::new(None) };
+    static PARSED_AT: Cell<Option<f64>> = const { Cell::new(None) };
+}
+
+/// Mark the input as parsed, only the first call in a run is used.
+pub fn parsed() {
+    if let Some(now) = CLOCK.get() {
+        if PARSED_AT.get().is_none() {
+            PARSED_AT.set(Some(now()));
+        }
+    }
+}
+
+/// Times of one run in milliseconds.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Times {
+    /// time until the solver called [`parsed`], `None` if it did not
+    pub parse: Option<f64>,
+    pub total: f64,
+}
+
+/// Run `solve` `runs` times, at least once, timing each run with `now`. Returns the answer of the
+/// last run, or the first error.
+pub fn time_runs(
+    now: Clock,
+    runs: u32,
+    mut solve: impl FnMut() -> Result<String>,
+) -> Result<(String, Vec<Times>)> {
+    CLOCK.set(Some(now));
+    let mut times = Vec::new();
+    let mut answer = Ok(String::new());
+    for _ in 0..runs.max(1) {
+        PARSED_AT.set(None);
+        let start = now();
+        answer = solve();
+        let end = now();
+        times.push(Times {
+            parse: PARSED_AT.get().map(|parsed| parsed - start),
+            total: end - start,
+        });
+        if answer.is_err() {
+            break;
+        }
+    }
+    CLOCK.set(None);
+    PARSED_AT.set(None);
+    answer.map(|answer| (answer, times))
+}
+
+/// Milliseconds since the first call, for timing native runs.
+#[cfg(feature = "cli")]
+pub fn native_clock() -> f64 {
+    use std::sync::OnceLock;
+    use std::time::Instant;
+
+    static START: OnceLock<Instant> = OnceLock::new();
+    START.get_or_init(Instant::now).elapsed().as_secs_f64() * 1000.0
+}
+
+/// Minimum, median and maximum of some times.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub min: f64,
+    pub median: f64,
+    pub max: f64,
+}
+impl Stats {
+    /// `None` if there are no times.
+    pub fn new(times: impl IntoIterator<Item = f64>) -> Option<Self> {
+        let mut times = times.into_iter().collect::<Vec<_>>();
+        if times.is_empty() {
+            return None;
+        }
+        times.sort_by(f64::total_cmp);
+        let mid = times.len() / 2;
+        let median = if times.len() % 2 == 0 {
+            (times[mid - 1] + times[mid]) / 2.0
+        } else {
+            times[mid]
+        };
+        Some(Self {
+            min: times[0],
+            median,
+            max: times[times.len() - 1],
+        })
+    }
+}
+
+/// Run times as shown by the cli and web app, so the two can be compared. A single run shows its
+/// time, more runs show the median with the min and max.
+pub struct Summary<'a>(pub &'a [Times]);
+impl fmt::Display for Summary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(total) = Stats::new(self.0.iter().map(|t| t.total)) else {
+            return Ok(());
+        };
+        // parse time is only shown if every run reported it
+        let parse = self
+            .0
+            .iter()
+            .map(|t| t.parse)
+            .collect::<Option<Vec<_>>>()
+            .and_then(Stats::new);
+        if self.0.len() == 1 {
+            write!(f, "{:.3}ms", total.median)?;
+            if let Some(parse) = parse {
+                write!(f, " (parse {:.3}ms)", parse.median)?;
+            }
+        } else {
+            write!(
+                f,
+                "median {:.3}ms, min {:.3}ms, max {:.3}ms over {} runs",
+                total.median,
+                total.min,
+                total.max,
+                self.0.len()
+            )?;
+            if let Some(parse) = parse {
+                write!(f, " (parse median {:.3}ms)", parse.median)?;
+            }
+        }
+        Ok(())
+    }
+}
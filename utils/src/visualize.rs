@@ -0,0 +1,42 @@
+//! Grid visualizations for solvers.
+//!
+//! Grid days have a `visualize` function returning [`Frames`], the grid at interesting points of
+//! the solve with each cell coloured from a palette, e.g. rendered on a canvas by the wasm-runner.
+
+/// Most frames kept, once reached further frames replace the last so the final state is shown.
+pub const MAX_FRAMES: usize = 500;
+
+/// Frames of a `width` by `height` grid.
+#[derive(Clone, Debug, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct Frames {
+    pub width: usize,
+    pub height: usize,
+    /// css colour of each cell value
+    pub palette: Vec<String>,
+    /// cell values of each frame, row by row
+    pub frames: Vec<Vec<u8>>,
+}
+
+impl Frames {
+    pub fn new(width: usize, height: usize, palette: &[&str]) -> Self {
+        Self {
+            width,
+            height,
+            palette: palette.iter().map(|c| c.to_string()).collect(),
+            frames: Vec::new(),
+        }
+    }
+
+    /// Add a frame, `cell` gives the palette index of each (row, column).
+    pub fn push(&mut self, cell: impl Fn(usize, usize) -> u8) {
+        let frame = (0..self.height)
+            .flat_map(|r| (0..self.width).map(move |c| (r, c)))
+            .map(|(r, c)| cell(r, c))
+            .collect();
+        if self.frames.len() == MAX_FRAMES {
+            self.frames.pop();
+        }
+        self.frames.push(frame);
+    }
+}
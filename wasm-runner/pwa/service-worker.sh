@@ -0,0 +1,14 @@
+#!/bin/sh
+# Trunk post build hook, writes sw.js into the build from the sw.js.in template with the list of
+# built files to cache. The version is a checksum of the built files, so any change to the app or
+# the solver crates makes a new service worker that replaces the old cache.
+set -eu
+
+template="$(cd "$(dirname "$0")" && pwd)/sw.js.in"
+cd "$TRUNK_STAGING_DIR"
+
+files=$(find . -type f ! -name sw.js | sed 's|^\./||' | sort)
+version=$( (echo "$files"; echo "$files" | xargs cat) | cksum | cut -d ' ' -f 1)
+assets=$(echo "$files" | sed 's/.*/"&"/' | paste -s -d , -)
+
+sed -e "s|__VERSION__|$version|" -e "s|__ASSETS__|$assets|" "$template" > sw.js
@@ -1,29 +1,580 @@
 use crate::data;
-use chrono::Local;
-use yew_agent::prelude::*;
-
-#[oneshot]
-pub async fn SolveOneTask(input: (u8, String)) -> Result<(String, i64), String> {
-    let days = data::day_solvers();
-    let day = days
-        .iter()
-        .find(|d| d.day == input.0)
-        .ok_or(format!("missing day {}", input.0))?;
-    let start = Local::now();
-    (day.solve_one)(&input.1)
-        .map(|r| (r, (Local::now() - start).num_milliseconds()))
-        .map_err(|e| e.to_string())
-}
-
-#[oneshot]
-pub async fn SolveTwoTask(input: (u8, String)) -> Result<(String, i64), String> {
-    let days = data::day_solvers();
-    let day = days
-        .iter()
-        .find(|d| d.day == input.0)
-        .ok_or(format!("missing day {}", input.0))?;
-    let start = Local::now();
-    (day.solve_two)(&input.1)
-        .map(|r| (r, (Local::now() - start).num_milliseconds()))
-        .map_err(|e| e.to_string())
+use js_sys::{Array, Date};
+use serde::{Deserialize, Serialize};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::fmt;
+use std::rc::{Rc, Weak};
+use utils::explain::{self, Explanation, Section};
+use utils::input;
+use utils::log::{self, Level};
+use utils::progress::{self, Progress};
+use utils::timing::{self, Stats, Summary, Times};
+use utils::visualize::Frames;
+use wasm_bindgen::{closure::Closure, JsCast, JsValue};
+use web_sys::{
+    Blob, BlobPropertyBag, DedicatedWorkerGlobalScope, MessageEvent, Url, Worker, WorkerGlobalScope,
+};
+use yew::Callback;
+
+/// Request to solve a day's part, sent to a solver worker.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct SolveRequest {
+    pub day: u8,
+    pub part: u8,
+    /// solver variant, `None` runs the default, see `data::Solver`
+    pub variant: Option<String>,
+    pub input: String,
+    /// number of timed runs, at least one is run
+    pub runs: u32,
+    /// collect an explanation from an extra untimed run, see `utils::explain`
+    pub explain: bool,
+    /// forward solver log messages up to this level to the browser console, see `utils::log`
+    pub log: Option<Level>,
+}
+
+/// Request to visualize a day's input, see `data::Visualizer`.
+#[derive(Debug, Deserialize, Serialize)]
+pub struct VisualizeRequest {
+    pub day: u8,
+    pub input: String,
+}
+
+/// Messages sent to a worker.
+#[derive(Debug, Deserialize, Serialize)]
+pub enum WorkerRequest {
+    Solve(SolveRequest),
+    Visualize(VisualizeRequest),
+}
+
+/// Progress reported by a solver while it is running, see `utils::progress`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SolveProgress {
+    pub done: u64,
+    pub total: Option<u64>,
+    pub value: Option<String>,
+}
+impl From<&Progress> for SolveProgress {
+    fn from(p: &Progress) -> Self {
+        Self {
+            done: p.done,
+            total: p.total,
+            value: p.value.clone(),
+        }
+    }
+}
+
+/// Run times of one run in milliseconds, see `utils::timing::Times`.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SolveTimes {
+    pub parse: Option<f64>,
+    pub total: f64,
+}
+impl From<&Times> for SolveTimes {
+    fn from(t: &Times) -> Self {
+        Self {
+            parse: t.parse,
+            total: t.total,
+        }
+    }
+}
+impl From<&SolveTimes> for Times {
+    fn from(t: &SolveTimes) -> Self {
+        Self {
+            parse: t.parse,
+            total: t.total,
+        }
+    }
+}
+
+/// Section of a solve's explanation, see `utils::explain::Section`.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SolveSection {
+    pub title: String,
+    pub lines: Vec<String>,
+}
+impl From<Section> for SolveSection {
+    fn from(s: Section) -> Self {
+        Self {
+            title: s.title,
+            lines: s.lines,
+        }
+    }
+}
+
+/// Answer of a solve, with the times of each run.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct Solution {
+    pub answer: String,
+    pub times: Vec<SolveTimes>,
+    /// empty unless the solve was explained
+    pub explanation: Vec<SolveSection>,
+}
+impl Solution {
+    /// Median run time in milliseconds.
+    pub fn ms(&self) -> f64 {
+        Stats::new(self.times.iter().map(|t| t.total))
+            .map(|s| s.median)
+            .unwrap_or_default()
+    }
+
+    /// Run times formatted the same as the cli `--runs` option.
+    pub fn summary(&self) -> String {
+        let times = self.times.iter().map(Times::from).collect::<Vec<_>>();
+        Summary(&times).to_string()
+    }
+}
+
+/// Error from a solver or visualizer, with the `eyre` chain and where in the input it happened.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SolveError {
+    pub message: String,
+    /// causes of the error, outermost first
+    pub chain: Vec<String>,
+    /// line, and column if known, numbered from 1, see `utils::input`
+    pub location: Option<(usize, Option<usize>)>,
+}
+impl From<&eyre::Report> for SolveError {
+    fn from(report: &eyre::Report) -> Self {
+        Self {
+            message: report.to_string(),
+            chain: report.chain().skip(1).map(|e| e.to_string()).collect(),
+            location: input::location(report).map(|l| (l.line, l.column)),
+        }
+    }
+}
+impl From<String> for SolveError {
+    fn from(message: String) -> Self {
+        Self {
+            message,
+            chain: Vec::new(),
+            location: None,
+        }
+    }
+}
+impl fmt::Display for SolveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)?;
+        for cause in &self.chain {
+            write!(f, ": {}", cause)?;
+        }
+        Ok(())
+    }
+}
+
+/// Messages sent back from a solver worker.
+#[derive(Debug, Deserialize, Serialize)]
+pub enum SolveResponse {
+    /// worker has loaded its wasm and can take a request
+    Ready,
+    Progress(SolveProgress),
+    Solved(Result<Solution, SolveError>),
+    Visualized(Result<Frames, SolveError>),
+}
+
+/// Minimum time between progress messages, so fast loops do not flood the page.
+const PROGRESS_INTERVAL_MS: f64 = 100.0;
+
+/// Clock for timing solves, `performance.now()` has microsecond resolution where `Date` only has
+/// milliseconds.
+fn performance_now() -> f64 {
+    js_sys::global()
+        .unchecked_into::<WorkerGlobalScope>()
+        .performance()
+        .map(|p| p.now())
+        .unwrap_or_else(Date::now)
+}
+
+fn solve(request: SolveRequest) -> Result<Solution, SolveError> {
+    let solver = data::solver(request.day, request.part, request.variant.as_deref())?;
+    if let Some(level) = request.log {
+        log::set_logger(level, |record| {
+            let message = JsValue::from(record.to_string());
+            match record.level {
+                Level::Info => web_sys::console::info_1(&message),
+                Level::Debug => web_sys::console::debug_1(&message),
+            }
+        });
+    }
+    let _span = log::span(format!("day{} part {}", request.day, request.part));
+    let (answer, times) = timing::time_runs(performance_now, request.runs, || {
+        (solver.solve)(&request.input)
+    })
+    .map_err(|e| SolveError::from(&e))?;
+    // explaining slows the solve, so the timed runs are never explained
+    let explanation = if request.explain {
+        let (explained, explanation) = explain::collect(|| (solver.solve)(&request.input));
+        explained.map_err(|e| SolveError::from(&e))?;
+        explanation
+    } else {
+        Explanation::default()
+    };
+    Ok(Solution {
+        answer,
+        times: times.iter().map(SolveTimes::from).collect(),
+        explanation: explanation.0.into_iter().map(SolveSection::from).collect(),
+    })
+}
+
+fn visualize(request: VisualizeRequest) -> Result<Frames, SolveError> {
+    let visualizer =
+        data::visualizer(request.day).ok_or(format!("no visualization for day {}", request.day))?;
+    (visualizer.visualize)(&request.input).map_err(|e| SolveError::from(&e))
+}
+
+fn post(scope: &DedicatedWorkerGlobalScope, response: &SolveResponse) {
+    if let Ok(msg) = serde_wasm_bindgen::to_value(response) {
+        let _ = scope.post_message(&msg);
+    }
+}
+
+/// Run inside the worker, solves or visualizes each request posted to it.
+pub fn register_solver() {
+    let scope: DedicatedWorkerGlobalScope = js_sys::global().unchecked_into();
+
+    let on_message = {
+        let scope = scope.clone();
+        Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+            let last_post = Cell::new(f64::MIN);
+            let progress_scope = scope.clone();
+            progress::set_reporter(move |p| {
+                let now = Date::now();
+                if now - last_post.get() >= PROGRESS_INTERVAL_MS {
+                    last_post.set(now);
+                    post(&progress_scope, &SolveResponse::Progress(p.into()));
+                }
+            });
+            let response = match serde_wasm_bindgen::from_value(event.data()) {
+                Ok(WorkerRequest::Solve(request)) => SolveResponse::Solved(solve(request)),
+                Ok(WorkerRequest::Visualize(request)) => {
+                    SolveResponse::Visualized(visualize(request))
+                }
+                Err(e) => SolveResponse::Solved(Err(e.to_string().into())),
+            };
+            progress::clear_reporter();
+            log::clear_logger();
+            post(&scope, &response);
+        })
+    };
+    scope.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    on_message.forget();
+
+    post(&scope, &SolveResponse::Ready);
+}
+
+/// Callbacks for a job submitted to the pool.
+pub struct SolveCallbacks {
+    /// a worker has picked up the job
+    pub on_start: Callback<()>,
+    pub on_progress: Callback<SolveProgress>,
+    pub on_solved: Callback<Result<Solution, SolveError>>,
+}
+
+enum JobCallbacks {
+    Solve(SolveCallbacks),
+    Visualize(Callback<Result<Frames, SolveError>>),
+}
+impl JobCallbacks {
+    fn start(&self) -> Option<Callback<()>> {
+        match self {
+            Self::Solve(callbacks) => Some(callbacks.on_start.clone()),
+            Self::Visualize(_) => None,
+        }
+    }
+
+    fn fail(self, err: String) -> Box<dyn FnOnce()> {
+        let err = SolveError::from(err);
+        match self {
+            Self::Solve(callbacks) => Box::new(move || callbacks.on_solved.emit(Err(err))),
+            Self::Visualize(on_visualized) => Box::new(move || on_visualized.emit(Err(err))),
+        }
+    }
+}
+
+struct Job {
+    id: u64,
+    request: WorkerRequest,
+    callbacks: JobCallbacks,
+}
+
+struct PoolWorker {
+    id: u64,
+    worker: Worker,
+    _on_message: Closure<dyn Fn(MessageEvent)>,
+    // set once the worker has loaded its wasm
+    ready: bool,
+    job: Option<(u64, JobCallbacks)>,
+}
+impl Drop for PoolWorker {
+    fn drop(&mut self) {
+        self.worker.set_onmessage(None);
+        self.worker.terminate();
+    }
+}
+
+// Callbacks are only emitted once the pool is no longer borrowed, as the components they update
+// may submit or cancel jobs.
+type Emits = Vec<Box<dyn FnOnce()>>;
+
+struct Pool {
+    size: usize,
+    url: String,
+    workers: Vec<PoolWorker>,
+    queue: VecDeque<Job>,
+    next_id: u64,
+}
+impl Pool {
+    fn next_id(&mut self) -> u64 {
+        self.next_id += 1;
+        self.next_id
+    }
+
+    fn cancel(&mut self, id: u64) {
+        self.queue.retain(|job| job.id != id);
+        // a running solve cannot be interrupted, so its worker is terminated and later replaced
+        self.workers
+            .retain(|w| w.job.as_ref().is_none_or(|(job, _)| *job != id));
+    }
+}
+
+/// Pool of solver workers shared by the app. Jobs are queued and run on the first free worker,
+/// workers are started as needed up to the pool size and kept loaded for later jobs.
+#[derive(Clone)]
+pub struct WorkerPool(Rc<RefCell<Pool>>);
+impl PartialEq for WorkerPool {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.0, &other.0)
+    }
+}
+impl WorkerPool {
+    /// Pool running the worker script at `url`, with at most `size` workers.
+    pub fn new(url: String, size: usize) -> Self {
+        Self(Rc::new(RefCell::new(Pool {
+            size: size.max(1),
+            url,
+            workers: Vec::new(),
+            queue: VecDeque::new(),
+            next_id: 0,
+        })))
+    }
+
+    pub fn size(&self) -> usize {
+        self.0.borrow().size
+    }
+
+    /// Change the pool size, extra workers are stopped once their jobs finish.
+    pub fn set_size(&self, size: usize) {
+        self.0.borrow_mut().size = size.max(1);
+        run(dispatch(&self.0));
+    }
+
+    /// Queue the request, the returned job must be kept until it is solved as dropping it cancels
+    /// the job.
+    pub fn submit(&self, request: SolveRequest, callbacks: SolveCallbacks) -> SolveJob {
+        self.queue(
+            WorkerRequest::Solve(request),
+            JobCallbacks::Solve(callbacks),
+        )
+    }
+
+    /// Queue the visualization, as with `submit` dropping the returned job cancels it.
+    pub fn visualize(
+        &self,
+        request: VisualizeRequest,
+        on_visualized: Callback<Result<Frames, SolveError>>,
+    ) -> SolveJob {
+        self.queue(
+            WorkerRequest::Visualize(request),
+            JobCallbacks::Visualize(on_visualized),
+        )
+    }
+
+    fn queue(&self, request: WorkerRequest, callbacks: JobCallbacks) -> SolveJob {
+        let id = {
+            let mut pool = self.0.borrow_mut();
+            let id = pool.next_id();
+            pool.queue.push_back(Job {
+                id,
+                request,
+                callbacks,
+            });
+            id
+        };
+        run(dispatch(&self.0));
+        SolveJob {
+            id,
+            pool: Rc::downgrade(&self.0),
+        }
+    }
+}
+
+/// Default pool size, one worker per logical processor, but at least two so both parts of a day
+/// run at the same time.
+pub fn default_pool_size() -> usize {
+    web_sys::window()
+        .map(|w| w.navigator().hardware_concurrency() as usize)
+        .unwrap_or_default()
+        .max(2)
+}
+
+/// Handle to a job submitted to the pool, dropping it cancels the job.
+pub struct SolveJob {
+    id: u64,
+    pool: Weak<RefCell<Pool>>,
+}
+impl Drop for SolveJob {
+    fn drop(&mut self) {
+        if let Some(pool) = self.pool.upgrade() {
+            pool.borrow_mut().cancel(self.id);
+            run(dispatch(&pool));
+        }
+    }
+}
+
+fn run(emits: Emits) {
+    for emit in emits {
+        emit();
+    }
+}
+
+/// Hand queued jobs to free workers, and start or stop workers to match the queue and pool size.
+fn dispatch(pool: &Rc<RefCell<Pool>>) -> Emits {
+    let mut emits: Emits = Vec::new();
+    let mut guard = pool.borrow_mut();
+    let p = &mut *guard;
+
+    for worker in p.workers.iter_mut() {
+        if !worker.ready || worker.job.is_some() {
+            continue;
+        }
+        let Some(job) = p.queue.pop_front() else {
+            break;
+        };
+        let sent = serde_wasm_bindgen::to_value(&job.request)
+            .map_err(|e| e.to_string())
+            .and_then(|msg| {
+                worker
+                    .worker
+                    .post_message(&msg)
+                    .map_err(|e| format!("failed to send input - {:?}", e))
+            });
+        match sent {
+            Ok(()) => {
+                if let Some(on_start) = job.callbacks.start() {
+                    emits.push(Box::new(move || on_start.emit(())));
+                }
+                worker.job = Some((job.id, job.callbacks));
+            }
+            Err(e) => emits.push(job.callbacks.fail(e)),
+        }
+    }
+
+    // stop free workers over the pool size, after it has been made smaller
+    while p.workers.len() > p.size {
+        match p.workers.iter().position(|w| w.job.is_none()) {
+            Some(i) => drop(p.workers.remove(i)),
+            None => break,
+        }
+    }
+
+    let free = p.workers.iter().filter(|w| w.job.is_none()).count();
+    let wanted = p
+        .queue
+        .len()
+        .saturating_sub(free)
+        .min(p.size.saturating_sub(p.workers.len()));
+    for _ in 0..wanted {
+        let id = p.next_id();
+        match spawn_worker(&p.url, id, Rc::downgrade(pool)) {
+            Ok(worker) => p.workers.push(worker),
+            Err(e) => {
+                // workers cannot be started, so fail the queued jobs rather than leave them waiting
+                for job in p.queue.drain(..) {
+                    emits.push(
+                        job.callbacks
+                            .fail(format!("failed to spawn worker - {:?}", e)),
+                    );
+                }
+                break;
+            }
+        }
+    }
+
+    emits
+}
+
+fn spawn_worker(url: &str, id: u64, pool: Weak<RefCell<Pool>>) -> Result<PoolWorker, JsValue> {
+    let worker = create_worker(url)?;
+    let on_message = Closure::<dyn Fn(MessageEvent)>::new(move |event: MessageEvent| {
+        on_message(&pool, id, event)
+    });
+    worker.set_onmessage(Some(on_message.as_ref().unchecked_ref()));
+    Ok(PoolWorker {
+        id,
+        worker,
+        _on_message: on_message,
+        ready: false,
+        job: None,
+    })
+}
+
+fn on_message(pool: &Weak<RefCell<Pool>>, id: u64, event: MessageEvent) {
+    let Some(pool) = pool.upgrade() else {
+        return;
+    };
+    let mut emits: Emits = Vec::new();
+    {
+        let mut p = pool.borrow_mut();
+        let Some(worker) = p.workers.iter_mut().find(|w| w.id == id) else {
+            return;
+        };
+        let solved = match serde_wasm_bindgen::from_value(event.data()) {
+            Ok(SolveResponse::Ready) => {
+                worker.ready = true;
+                None
+            }
+            Ok(SolveResponse::Progress(progress)) => {
+                if let Some((_, JobCallbacks::Solve(callbacks))) = &worker.job {
+                    let on_progress = callbacks.on_progress.clone();
+                    emits.push(Box::new(move || on_progress.emit(progress)));
+                }
+                None
+            }
+            Ok(response) => Some(Ok(response)),
+            Err(e) => Some(Err(e.to_string())),
+        };
+        if let Some(response) = solved {
+            // the worker is free for the next job whatever the response
+            match (worker.job.take(), response) {
+                (Some((_, JobCallbacks::Solve(callbacks))), Ok(SolveResponse::Solved(res))) => {
+                    emits.push(Box::new(move || callbacks.on_solved.emit(res)))
+                }
+                (Some((_, JobCallbacks::Visualize(cb))), Ok(SolveResponse::Visualized(res))) => {
+                    emits.push(Box::new(move || cb.emit(res)))
+                }
+                (Some((_, callbacks)), Err(e)) => emits.push(callbacks.fail(e)),
+                (Some((_, callbacks)), Ok(_)) => {
+                    emits.push(callbacks.fail("unexpected response from worker".to_string()))
+                }
+                (None, _) => {}
+            }
+        }
+    }
+    emits.extend(dispatch(&pool));
+    run(emits);
+}
+
+// Trunk builds workers as a no-modules js shim plus wasm, load both from a blob script the same
+// way yew agents do.
+fn create_worker(path: &str) -> Result<Worker, JsValue> {
+    let href = web_sys::window().ok_or("no window")?.location().href()?;
+    let js_url = Url::new_with_base(path, &href)?.to_string();
+    let wasm_url = js_url.replace(".js", "_bg.wasm");
+
+    let script = Array::new();
+    script.push(&format!(r#"importScripts("{js_url}");wasm_bindgen("{wasm_url}");"#).into());
+    let options = BlobPropertyBag::new();
+    options.set_type("application/javascript");
+    let blob = Blob::new_with_str_sequence_and_options(&script, &options)?;
+    Worker::new(&Url::create_object_url_with_blob(&blob)?)
 }
@@ -0,0 +1,22 @@
+use yew::prelude::*;
+
+/// Whether an answer matches the known correct answer, ignoring surrounding whitespace.
+/// `None` if there is no known answer to check against.
+pub fn matches(known: Option<&str>, answer: &str) -> Option<bool> {
+    known.map(|known| known.trim() == answer.trim())
+}
+
+/// Mark for an answer checked against the known answer, empty if there is no known answer.
+pub fn check_html(known: Option<&str>, answer: &str) -> Html {
+    match matches(known, answer) {
+        Some(true) => html! {
+            <span style="color: green" title="matches the known answer">{" \u{2714}"}</span>
+        },
+        Some(false) => html! {
+            <span style="color: red">
+                {format!(" \u{2718} expected {}", known.unwrap_or_default().trim())}
+            </span>
+        },
+        None => html! {},
+    }
+}
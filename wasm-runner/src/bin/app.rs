@@ -1,3 +1,4 @@
 fn main() {
+    wasm_runner::pwa::register_service_worker();
     yew::Renderer::<wasm_runner::App>::new().render();
 }
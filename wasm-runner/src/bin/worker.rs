@@ -0,0 +1,3 @@
+fn main() {
+    wasm_runner::agent::register_solver();
+}
@@ -1,6 +0,0 @@
-use wasm_runner::agent::SolveOneTask;
-use yew_agent::Registrable;
-
-fn main() {
-    SolveOneTask::registrar().register();
-}
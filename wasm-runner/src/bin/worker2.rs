@@ -1,6 +0,0 @@
-use wasm_runner::agent::SolveTwoTask;
-use yew_agent::Registrable;
-
-fn main() {
-    SolveTwoTask::registrar().register();
-}
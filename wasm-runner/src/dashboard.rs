@@ -0,0 +1,358 @@
+use crate::agent::{
+    self, Solution, SolveCallbacks, SolveError, SolveJob, SolveRequest, WorkerPool,
+};
+use crate::answers;
+use crate::route::Route;
+use crate::storage::{self, SavedDay, SavedOutput};
+use std::collections::{BTreeMap, HashMap};
+use web_sys::{HtmlInputElement, HtmlSelectElement};
+use yew::prelude::*;
+
+#[derive(Clone, Debug, PartialEq)]
+enum Status {
+    NoInput,
+    NotRun,
+    Queued,
+    Running,
+    Finished(SavedOutput),
+}
+impl Status {
+    /// `known` is the known correct answer a solution is checked against.
+    fn to_html(&self, known: Option<&str>) -> Html {
+        match self {
+            Self::NoInput => html! {<>{"no input"}</>},
+            Self::NotRun => html! {<>{"-"}</>},
+            Self::Queued => html! {<>{"queued"}</>},
+            Self::Running => html! {<>{"running"}</>},
+            Self::Finished(SavedOutput::Cancelled) => html! {<>{"cancelled"}</>},
+            Self::Finished(SavedOutput::Skipped) => html! {<>{"not run"}</>},
+            Self::Finished(SavedOutput::Error(err)) => html! {<>{"ERROR - "}{err}</>},
+            Self::Finished(SavedOutput::Solution(answer, _)) => {
+                html! {<>{answer}{answers::check_html(known, answer)}</>}
+            }
+        }
+    }
+
+    fn ms(&self) -> Option<f64> {
+        match self {
+            Self::Finished(SavedOutput::Solution(_, ms)) => Some(*ms),
+            _ => None,
+        }
+    }
+
+    fn pending(&self) -> bool {
+        matches!(self, Self::Queued | Self::Running)
+    }
+}
+
+/// Background colour for a run time, green for the fastest through to red for the slowest. A log
+/// scale is used as run times range from under a millisecond to many seconds.
+fn heat(ms: f64, max: f64) -> String {
+    let scale = if max > 0.0 {
+        (ms + 1.0).ln() / (max + 1.0).ln()
+    } else {
+        0.0
+    };
+    format!(
+        "background-color: hsl({:.0}, 80%, 75%); color: black",
+        120.0 * (1.0 - scale)
+    )
+}
+
+#[derive(Debug)]
+pub enum Msg {
+    RunAll,
+    Cancel,
+    SortByTime(bool),
+    PoolSize(usize),
+    Started(usize, u8, u8),
+    Solved(usize, u8, u8, Result<Solution, SolveError>),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct DashboardProps {
+    pub days: Vec<u8>,
+}
+
+/// Runs both parts of every day with a saved input on the worker pool, and shows the answers and
+/// run times.
+pub struct Dashboard {
+    inputs: HashMap<u8, String>,
+    // known correct answers entered for each day
+    answers: HashMap<u8, (Option<String>, Option<String>)>,
+    // status of each (day, part)
+    results: BTreeMap<(u8, u8), Status>,
+    pool: WorkerPool,
+    // queued and running jobs, dropping a job cancels it
+    jobs: HashMap<(u8, u8), SolveJob>,
+    sort_by_time: bool,
+    // incremented every run, so results from earlier runs are ignored
+    run: usize,
+}
+impl Dashboard {
+    fn load(&mut self, days: &[u8]) {
+        self.inputs.clear();
+        self.answers.clear();
+        self.results.clear();
+        for &day in days {
+            let saved = storage::load(day);
+            let (one, two) = match saved.output {
+                _ if saved.input.is_empty() => (Status::NoInput, Status::NoInput),
+                Some((o1, o2)) => (Status::Finished(o1), Status::Finished(o2)),
+                None => (Status::NotRun, Status::NotRun),
+            };
+            self.results.insert((day, 1), one);
+            self.results.insert((day, 2), two);
+            if !saved.input.is_empty() {
+                self.inputs.insert(day, saved.input);
+            }
+            self.answers.insert(day, saved.answers);
+        }
+    }
+
+    fn status(&self, day: u8, part: u8) -> &Status {
+        self.results.get(&(day, part)).unwrap_or(&Status::NoInput)
+    }
+
+    fn submit(&mut self, ctx: &Context<Self>, day: u8, part: u8) {
+        let request = SolveRequest {
+            day,
+            part,
+            variant: None,
+            input: self.inputs.get(&day).cloned().unwrap_or_default(),
+            runs: 1,
+            explain: false,
+            log: None,
+        };
+        let run = self.run;
+        let callbacks = SolveCallbacks {
+            on_start: ctx.link().callback(move |_| Msg::Started(run, day, part)),
+            on_progress: Callback::noop(),
+            on_solved: ctx
+                .link()
+                .callback(move |res| Msg::Solved(run, day, part, res)),
+        };
+        let job = self.pool.submit(request, callbacks);
+        self.jobs.insert((day, part), job);
+        self.results.insert((day, part), Status::Queued);
+    }
+
+    /// Save the day's results once both parts are finished, so they show when the day is selected.
+    fn save(&self, day: u8) {
+        if let (Status::Finished(o1), Status::Finished(o2)) =
+            (self.status(day, 1), self.status(day, 2))
+        {
+            storage::save(
+                day,
+                &SavedDay {
+                    input: self.inputs.get(&day).cloned().unwrap_or_default(),
+                    output: Some((o1.clone(), o2.clone())),
+                    answers: self.answers.get(&day).cloned().unwrap_or_default(),
+                },
+            );
+        }
+    }
+
+    fn row(&self, day: u8, max: f64) -> Html {
+        let on_select = Callback::from(move |event: MouseEvent| {
+            event.prevent_default();
+            Route::Day { day, part: None }.navigate()
+        });
+        let time = |status: &Status| match status.ms() {
+            Some(ms) => html! {<td style={heat(ms, max)}>{format!("{:.3}", ms)}</td>},
+            None => html! {<td/>},
+        };
+        let (one, two) = (self.status(day, 1), self.status(day, 2));
+        let (known_one, known_two) = self.answers.get(&day).cloned().unwrap_or_default();
+        let total = one.ms().into_iter().chain(two.ms()).reduce(|a, b| a + b);
+        html! {
+            <tr key={day}>
+                <td>
+                    <a href={Route::Day { day, part: None }.href()} onclick={on_select}>
+                        {day}
+                    </a>
+                </td>
+                <td>{one.to_html(known_one.as_deref())}</td>
+                {time(one)}
+                <td>{two.to_html(known_two.as_deref())}</td>
+                {time(two)}
+                <td>{total.map(|t| format!("{:.3}", t)).unwrap_or_default()}</td>
+            </tr>
+        }
+    }
+}
+
+impl Component for Dashboard {
+    type Message = Msg;
+    type Properties = DashboardProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut dashboard = Self {
+            inputs: HashMap::new(),
+            answers: HashMap::new(),
+            results: BTreeMap::new(),
+            pool: ctx
+                .link()
+                .context::<WorkerPool>(Callback::noop())
+                .expect("worker pool context")
+                .0,
+            jobs: HashMap::new(),
+            sort_by_time: false,
+            run: 0,
+        };
+        dashboard.load(&ctx.props().days);
+        dashboard
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let running = !self.jobs.is_empty();
+        let sizes = (1..=agent::default_pool_size().max(self.pool.size()))
+            .map(|size| {
+                html! {
+                    <option value={size.to_string()} selected={size == self.pool.size()}>
+                        {size}
+                    </option>
+                }
+            })
+            .collect::<Html>();
+        let max = self
+            .results
+            .values()
+            .filter_map(Status::ms)
+            .fold(0.0, f64::max);
+        let total: f64 = self.results.values().filter_map(Status::ms).sum();
+
+        let mut days = ctx.props().days.clone();
+        if self.sort_by_time {
+            // slowest days first, days without any times last
+            let total = |day| {
+                let times = [self.status(day, 1).ms(), self.status(day, 2).ms()];
+                times.into_iter().flatten().reduce(|a, b| a + b)
+            };
+            days.sort_by(|&a, &b| match (total(a), total(b)) {
+                (Some(a), Some(b)) => b.total_cmp(&a),
+                (a, b) => b.is_some().cmp(&a.is_some()),
+            });
+        }
+        let rows = days.iter().map(|&day| self.row(day, max)).collect::<Html>();
+
+        html! {
+            <div>
+                <h3>{"Dashboard"}</h3>
+                <p>
+                    {format!(
+                        "{} of {} days have a saved input, total run time {:.3}ms",
+                        self.inputs.len(),
+                        ctx.props().days.len(),
+                        total
+                    )}
+                </p>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::RunAll)}
+                    disabled={self.inputs.is_empty()}
+                    type="button">{"Run all"}
+                </button>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::Cancel)}
+                    disabled={!running}
+                    type="button">{"Cancel"}
+                </button>
+                <label>
+                    <input
+                        type="checkbox"
+                        checked={self.sort_by_time}
+                        onchange={ctx.link().callback(|event: Event| {
+                            let input: HtmlInputElement = event.target_unchecked_into();
+                            Msg::SortByTime(input.checked())
+                        })}/>
+                    {" Sort by run time"}
+                </label>
+                <label>
+                    {"Workers "}
+                    <select onchange={ctx.link().callback(|event: Event| {
+                        let select: HtmlSelectElement = event.target_unchecked_into();
+                        Msg::PoolSize(select.value().parse().unwrap_or(1))
+                    })}>
+                        {sizes}
+                    </select>
+                </label>
+                <table>
+                    <thead>
+                        <tr>
+                            <th>{"Day"}</th>
+                            <th>{"Part One"}</th>
+                            <th>{"ms"}</th>
+                            <th>{"Part Two"}</th>
+                            <th>{"ms"}</th>
+                            <th>{"Total ms"}</th>
+                        </tr>
+                    </thead>
+                    <tbody>{rows}</tbody>
+                </table>
+            </div>
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        match msg {
+            Msg::RunAll => {
+                // inputs may have been changed on a day's page since the dashboard was opened
+                self.jobs.clear();
+                self.run += 1;
+                self.load(&ctx.props().days);
+                let mut days = self.inputs.keys().copied().collect::<Vec<_>>();
+                days.sort_unstable();
+                for day in days {
+                    self.submit(ctx, day, 1);
+                    self.submit(ctx, day, 2);
+                }
+                true
+            }
+            Msg::Cancel => {
+                self.jobs.clear();
+                let mut days = Vec::new();
+                for (&(day, _), status) in self.results.iter_mut() {
+                    if status.pending() {
+                        *status = Status::Finished(SavedOutput::Cancelled);
+                        days.push(day);
+                    }
+                }
+                days.dedup();
+                for day in days {
+                    self.save(day);
+                }
+                true
+            }
+            Msg::SortByTime(sort_by_time) => {
+                self.sort_by_time = sort_by_time;
+                true
+            }
+            Msg::PoolSize(size) => {
+                self.pool.set_size(size);
+                storage::save_pool_size(size);
+                true
+            }
+            Msg::Started(run, day, part) => {
+                if run == self.run && self.jobs.contains_key(&(day, part)) {
+                    self.results.insert((day, part), Status::Running);
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::Solved(run, day, part, res) => {
+                if run != self.run || self.jobs.remove(&(day, part)).is_none() {
+                    // cancelled or replaced by a later run
+                    return false;
+                }
+                let output = match res {
+                    Ok(sol) => SavedOutput::Solution(sol.answer.clone(), sol.ms()),
+                    Err(err) => SavedOutput::Error(err.to_string()),
+                };
+                self.results.insert((day, part), Status::Finished(output));
+                self.save(day);
+                true
+            }
+        }
+    }
+}
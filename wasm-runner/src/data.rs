@@ -1,53 +1,121 @@
 use eyre::Result;
+use std::sync::OnceLock;
+use utils::visualize::Frames;
 
-#[derive(Clone, PartialEq)]
-pub struct Day {
+/// Variant every day's parts have, the solution run by the day's binary.
+pub const DEFAULT_VARIANT: &str = "default";
+
+/// A solver for one part of a day. Parts can have more than one variant, such as an alternative
+/// approach to compare against the default.
+#[derive(Clone)]
+pub struct Solver {
     pub day: u8,
-    pub solve_one: fn(&str) -> Result<String>,
-    pub solve_two: fn(&str) -> Result<String>,
+    pub part: u8,
+    pub variant: &'static str,
+    pub solve: fn(&str) -> Result<String>,
 }
 macro_rules! day {
     ($i: literal) => {
         paste::paste! {
-            Day {
-                day: $i,
-                solve_one: [<day $i>]::solve_one,
-                solve_two: [<day $i>]::solve_two,
-            }
+            [
+                Solver {
+                    day: $i,
+                    part: 1,
+                    variant: DEFAULT_VARIANT,
+                    solve: [<day $i>]::solve_one,
+                },
+                Solver {
+                    day: $i,
+                    part: 2,
+                    variant: DEFAULT_VARIANT,
+                    solve: [<day $i>]::solve_two,
+                },
+            ]
         }
     };
 }
 
 pub fn day_numbers() -> Vec<u8> {
-    day_solvers().iter().map(|d| d.day).collect()
+    let mut days = solvers().iter().map(|s| s.day).collect::<Vec<_>>();
+    days.dedup();
+    days
+}
+
+/// Solver for the day's part, `None` picks the default variant.
+pub fn solver(day: u8, part: u8, variant: Option<&str>) -> Result<&'static Solver, String> {
+    let variant = variant.unwrap_or(DEFAULT_VARIANT);
+    solvers()
+        .iter()
+        .find(|s| s.day == day && s.part == part && s.variant == variant)
+        .ok_or(format!("missing day {} part {} variant {}", day, part, variant))
+}
+
+/// All solvers, built once on first use.
+pub fn solvers() -> &'static [Solver] {
+    static SOLVERS: OnceLock<Vec<Solver>> = OnceLock::new();
+    SOLVERS.get_or_init(|| {
+        [
+            day!(1),
+            day!(2),
+            day!(3),
+            day!(4),
+            day!(5),
+            day!(6),
+            day!(7),
+            day!(8),
+            day!(9),
+            day!(10),
+            day!(11),
+            day!(12),
+            day!(13),
+            day!(14),
+            day!(15),
+            day!(16),
+            day!(17),
+            day!(18),
+            day!(19),
+            day!(20),
+            day!(21),
+            day!(22),
+            day!(23),
+            day!(24),
+            day!(25),
+        ]
+        .concat()
+    })
+}
+
+/// Grid visualization of a day, see `utils::visualize`.
+#[derive(Clone)]
+pub struct Visualizer {
+    pub day: u8,
+    pub visualize: fn(&str) -> Result<Frames>,
+}
+macro_rules! visualizer {
+    ($i: literal) => {
+        paste::paste! {
+            Visualizer {
+                day: $i,
+                visualize: [<day $i>]::visualize,
+            }
+        }
+    };
 }
 
-pub fn day_solvers() -> Vec<Day> {
-    vec![
-        day!(1),
-        day!(2),
-        day!(3),
-        day!(4),
-        day!(5),
-        day!(6),
-        day!(7),
-        day!(8),
-        day!(9),
-        day!(10),
-        day!(11),
-        day!(12),
-        day!(13),
-        day!(14),
-        day!(15),
-        day!(16),
-        day!(17),
-        day!(18),
-        day!(19),
-        day!(20),
-        day!(21),
-        day!(22),
-        day!(23),
-        day!(24),
-        day!(25),
-    ]
+/// Visualizer for the day, if it has one.
+pub fn visualizer(day: u8) -> Option<&'static Visualizer> {
+    static VISUALIZERS: OnceLock<Vec<Visualizer>> = OnceLock::new();
+    VISUALIZERS
+        .get_or_init(|| {
+            vec![
+                visualizer!(10),
+                visualizer!(14),
+                visualizer!(16),
+                visualizer!(17),
+                visualizer!(21),
+                visualizer!(23),
+            ]
+        })
+        .iter()
+        .find(|v| v.day == day)
 }
@@ -1,27 +1,36 @@
+use crate::route::Route;
 use yew::prelude::*;
 
 #[derive(Properties, PartialEq)]
 pub struct DaysListProps {
     pub days: Vec<u8>,
-    pub on_click: Callback<u8>,
+    pub on_click: Callback<Route>,
 }
 
 #[function_component(DaysList)]
 pub fn days_list(DaysListProps { days, on_click }: &DaysListProps) -> Html {
-    //let on_click = on_click.clone();
+    let link = |route: Route, label: String| {
+        let on_select = {
+            let on_click = on_click.clone();
+            let route = route.clone();
+            Callback::from(move |event: MouseEvent| {
+                // navigate in page rather than loading the link
+                event.prevent_default();
+                on_click.emit(route.clone())
+            })
+        };
+        html! {
+            <a key={label.clone()} href={route.href()} onclick={on_select}>{label}</a>
+        }
+    };
     let days_list = days
         .iter()
-        .map(|&day| {
-            let on_day_select = {
-                let on_click = on_click.clone();
-                Callback::from(move |_| on_click.emit(day))
-            };
-            html! {
-                <a key={day} onclick={on_day_select}>{format!("day {}", day)}</a>
-            }
-        })
+        .map(|&day| link(Route::Day { day, part: None }, format!("day {}", day)))
         .collect::<Html>();
     html! {
-        <nav>{ days_list }</nav>
+        <nav>
+            { days_list }
+            { link(Route::Dashboard, "dashboard".to_string()) }
+        </nav>
     }
 }
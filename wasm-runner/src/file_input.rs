@@ -0,0 +1,105 @@
+use wasm_bindgen::{closure::Closure, JsCast};
+use web_sys::{DragEvent, Event, File, FileReader, HtmlInputElement, ProgressEvent};
+use yew::prelude::*;
+
+#[derive(Properties, PartialEq)]
+pub struct FileInputProps {
+    pub on_load: Callback<String>,
+}
+
+fn read_file(file: File, on_load: Callback<String>, on_error: Callback<String>) {
+    let reader = match FileReader::new() {
+        Ok(reader) => reader,
+        Err(e) => return on_error.emit(format!("cannot read files - {:?}", e)),
+    };
+    let onload = {
+        let reader = reader.clone();
+        let on_error = on_error.clone();
+        let name = file.name();
+        Closure::once_into_js(move |_: ProgressEvent| {
+            match reader.result().ok().and_then(|r| r.as_string()) {
+                Some(text) => on_load.emit(text),
+                None => on_error.emit(format!("failed to read {}", name)),
+            }
+        })
+    };
+    reader.set_onload(Some(onload.unchecked_ref()));
+    if let Err(e) = reader.read_as_text(&file) {
+        on_error.emit(format!("failed to read {} - {:?}", file.name(), e));
+    }
+}
+
+/// File picker and drag and drop zone, the contents of the chosen file are passed to `on_load`.
+#[function_component(FileInput)]
+pub fn file_input(FileInputProps { on_load }: &FileInputProps) -> Html {
+    let dragging = use_state(|| false);
+    let error = use_state(|| None);
+
+    let on_error = {
+        let error = error.clone();
+        Callback::from(move |e: String| error.set(Some(e)))
+    };
+    let on_file = {
+        let on_load = on_load.clone();
+        let error = error.clone();
+        Callback::from(move |file: File| {
+            error.set(None);
+            read_file(file, on_load.clone(), on_error.clone());
+        })
+    };
+
+    let on_change = {
+        let on_file = on_file.clone();
+        Callback::from(move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            if let Some(file) = input.files().and_then(|files| files.get(0)) {
+                on_file.emit(file);
+            }
+        })
+    };
+    let on_drag_over = {
+        let dragging = dragging.clone();
+        Callback::from(move |event: DragEvent| {
+            // default must be prevented for the drop event to fire
+            event.prevent_default();
+            dragging.set(true);
+        })
+    };
+    let on_drag_leave = {
+        let dragging = dragging.clone();
+        Callback::from(move |_: DragEvent| dragging.set(false))
+    };
+    let on_drop = {
+        let dragging = dragging.clone();
+        Callback::from(move |event: DragEvent| {
+            event.prevent_default();
+            dragging.set(false);
+            if let Some(file) = event
+                .data_transfer()
+                .and_then(|data| data.files())
+                .and_then(|files| files.get(0))
+            {
+                on_file.emit(file);
+            }
+        })
+    };
+
+    let style = if *dragging {
+        "border: 2px dashed; padding: 1em; text-align: center; opacity: 0.6"
+    } else {
+        "border: 2px dashed; padding: 1em; text-align: center"
+    };
+    html! {
+        <div
+            style={style}
+            ondragover={on_drag_over}
+            ondragleave={on_drag_leave}
+            ondrop={on_drop}>
+            {"Drop an input file here, or "}
+            <input type="file" accept=".txt,text/plain" onchange={on_change}/>
+            if let Some(e) = &*error {
+                <p>{"ERROR - "}{e}</p>
+            }
+        </div>
+    }
+}
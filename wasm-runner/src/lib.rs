@@ -1,10 +1,21 @@
 pub mod agent;
+mod answers;
+mod dashboard;
 mod data;
 mod days_list;
+mod file_input;
+pub mod pwa;
+mod route;
 mod run_button;
 mod runner;
+mod storage;
+mod visualization;
 
+use agent::WorkerPool;
+use dashboard::Dashboard;
 use days_list::DaysList;
+use gloo_history::{BrowserHistory, History};
+use route::Route;
 use runner::Runner;
 use yew::prelude::*;
 
@@ -12,26 +23,45 @@ use yew::prelude::*;
 pub fn app() -> Html {
     let days = data::day_numbers();
 
-    // call backwhen day is selected
-    let selected_day = use_state(|| None);
-    let on_day_select = {
-        let selected_day = selected_day.clone();
-        Callback::from(move |day: u8| selected_day.set(Some(day)))
-    };
-    // start runner for that day
-    let runner = selected_day.as_ref().map(|&day| {
-        html! {
-            <Runner day={day}/>
-        }
+    // one pool of solver workers for all pages, so loaded workers are reused between runs
+    let pool = use_memo((), |_| {
+        let size = storage::load_pool_size().unwrap_or_else(agent::default_pool_size);
+        WorkerPool::new(format!("{}/worker.js", route::base_path()), size)
     });
 
+    // route is kept in sync with the browser history, including back and forward
+    let route = use_state(Route::current);
+    {
+        let route = route.clone();
+        use_effect_with((), move |_| {
+            let listener = BrowserHistory::new().listen(move || route.set(Route::current()));
+            move || drop(listener)
+        });
+    }
+    let on_select = Callback::from(|route: Route| route.navigate());
+    let page = match *route {
+        Route::Home => html! {},
+        Route::Dashboard => html! {
+            <Dashboard days={days.clone()}/>
+        },
+        // start runner for the routed day
+        Route::Day { day, part } if days.contains(&day) => {
+            let on_part_select =
+                Callback::from(move |part: Option<u8>| Route::Day { day, part }.navigate());
+            html! {
+                <Runner day={day} part={part} on_part_select={on_part_select}/>
+            }
+        }
+        Route::Day { .. } => html! {},
+    };
+
     html! {
-        <>
+        <ContextProvider<WorkerPool> context={(*pool).clone()}>
             <header>
                 <h1>{ "AOC 2023 Solver" }</h1>
-                <DaysList days={days} on_click={on_day_select.clone()}/>
+                <DaysList days={days} on_click={on_select}/>
             </header>
-            { for runner }
-        </>
+            { page }
+        </ContextProvider<WorkerPool>>
     }
 }
@@ -0,0 +1,22 @@
+use crate::route;
+use wasm_bindgen::JsValue;
+
+/// Register the service worker the trunk build generates, see `pwa/service-worker.sh`, so the app
+/// can be used offline. Only release builds register it, so `trunk serve` never serves a stale
+/// cached build.
+pub fn register_service_worker() {
+    if cfg!(debug_assertions) {
+        return;
+    }
+    let Some(navigator) = web_sys::window().map(|w| w.navigator()) else {
+        return;
+    };
+    // service workers are only available on https and localhost
+    if !js_sys::Reflect::has(&navigator, &JsValue::from_str("serviceWorker")).unwrap_or(false) {
+        return;
+    }
+    // a failed registration only means the app is not available offline
+    let _ = navigator
+        .service_worker()
+        .register(&format!("{}/sw.js", route::base_path()));
+}
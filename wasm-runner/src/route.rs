@@ -0,0 +1,118 @@
+use gloo_history::{BrowserHistory, History};
+use serde::Deserialize;
+
+/// Path the app is served from, without a trailing `/`. Trunk sets the `<base>` element in
+/// `index.html` to the `--public-url` it was built with.
+pub fn base_path() -> String {
+    web_sys::window()
+        .and_then(|w| w.document())
+        .and_then(|d| d.base_uri().ok().flatten())
+        .and_then(|uri| web_sys::Url::new(&uri).ok())
+        .map(|url| url.pathname().trim_end_matches('/').to_string())
+        .unwrap_or_default()
+}
+
+/// Longest encoded input that will be put in a share link.
+const MAX_SHARE_LEN: usize = 4000;
+
+const INPUT_FRAGMENT: &str = "#input=";
+
+/// Selected page, either the dashboard at `{base}/dashboard` or a day at
+/// `{base}/day/{day}?part={part}`. A day with no part runs both parts.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub enum Route {
+    #[default]
+    Home,
+    Dashboard,
+    Day { day: u8, part: Option<u8> },
+}
+
+#[derive(Deserialize)]
+struct PartQuery {
+    part: Option<u8>,
+}
+
+impl Route {
+    /// Route for the current browser location.
+    pub fn current() -> Self {
+        let location = BrowserHistory::new().location();
+        let page = location
+            .path()
+            .strip_prefix(&base_path())
+            .map(|p| p.trim_matches('/'))
+            .unwrap_or_default();
+        if page == "dashboard" {
+            return Self::Dashboard;
+        }
+        match page.strip_prefix("day/").and_then(|d| d.parse::<u8>().ok()) {
+            Some(day) => {
+                let part = location
+                    .query::<PartQuery>()
+                    .ok()
+                    .and_then(|q| q.part)
+                    .filter(|p| *p == 1 || *p == 2);
+                Self::Day { day, part }
+            }
+            None => Self::Home,
+        }
+    }
+
+    pub fn path(&self) -> String {
+        let base = base_path();
+        match self {
+            Self::Home => format!("{}/", base),
+            Self::Dashboard => format!("{}/dashboard", base),
+            Self::Day { day, .. } => format!("{}/day/{}", base, day),
+        }
+    }
+
+    /// Path with query, as used in links.
+    pub fn href(&self) -> String {
+        match self {
+            Self::Day {
+                part: Some(part), ..
+            } => format!("{}?part={}", self.path(), part),
+            _ => self.path(),
+        }
+    }
+
+    /// Push the route onto the browser history, listeners of the history are notified.
+    pub fn navigate(&self) {
+        BrowserHistory::new().push(self.href());
+    }
+}
+
+/// Input shared in the current URL fragment, if any. The fragment is removed once read so a
+/// shared input only replaces the saved input once.
+pub fn take_shared_input() -> Option<String> {
+    let history = BrowserHistory::new();
+    let location = history.location();
+    let encoded = location.hash().strip_prefix(INPUT_FRAGMENT)?;
+    let input = js_sys::decode_uri_component(encoded).ok()?.into();
+    history.replace(format!("{}{}", location.path(), location.query_str()));
+    Some(input)
+}
+
+/// Link to the route with the input encoded in the URL fragment.
+pub fn share_link(route: &Route, input: &str) -> Result<String, String> {
+    let encoded = String::from(js_sys::encode_uri_component(input));
+    if encoded.len() > MAX_SHARE_LEN {
+        return Err(format!(
+            "input too large to share, {} encoded characters over the {} limit",
+            encoded.len(),
+            MAX_SHARE_LEN
+        ));
+    }
+    let origin = web_sys::window()
+        .ok_or("no window")?
+        .location()
+        .origin()
+        .map_err(|e| format!("{:?}", e))?;
+    Ok(format!(
+        "{}{}{}{}",
+        origin,
+        route.href(),
+        INPUT_FRAGMENT,
+        encoded
+    ))
+}
@@ -1,64 +1,190 @@
-use crate::agent::{SolveOneTask, SolveTwoTask};
-use yew::platform::spawn_local;
+use utils::log::Level;
+use web_sys::{HtmlInputElement, HtmlSelectElement};
 use yew::prelude::*;
-use yew_agent::oneshot::use_oneshot_runner;
+
+/// Timeout options in seconds, `None` runs until the solver finishes.
+const TIMEOUTS: [Option<u32>; 5] = [None, Some(10), Some(30), Some(60), Some(300)];
+
+/// Part options, `None` runs both parts.
+const PARTS: [Option<u8>; 3] = [None, Some(1), Some(2)];
+
+/// Number of timed runs options, more runs give steadier run times.
+const RUNS: [u32; 5] = [1, 5, 10, 25, 100];
+
+/// Log level options, `None` logs nothing so the run times are not skewed by logging.
+const LOG_LEVELS: [Option<Level>; 3] = [None, Some(Level::Info), Some(Level::Debug)];
+
+/// Options picked for a run.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct RunOptions {
+    /// timeout in seconds, `None` runs until the solver finishes
+    pub timeout: Option<u32>,
+    pub runs: u32,
+    /// collect an explanation of the solve, see `utils::explain`
+    pub explain: bool,
+    /// forward solver log messages up to this level to the browser console
+    pub log: Option<Level>,
+}
 
 #[derive(Properties, PartialEq)]
 pub struct RunButtonProps {
-    pub day: u8,
-    pub input: String,
-    pub run_callback: Callback<()>,
-    pub solve_one_callback: Callback<Result<(String, i64), String>>,
-    pub solve_two_callback: Callback<Result<(String, i64), String>>,
+    pub running: bool,
+    pub part: Option<u8>,
+    pub part_callback: Callback<Option<u8>>,
+    pub run_callback: Callback<RunOptions>,
+    pub cancel_callback: Callback<()>,
 }
 
 #[function_component(RunButton)]
 pub fn run_button(
     RunButtonProps {
-        day,
-        input,
+        running,
+        part,
+        part_callback,
         run_callback,
-        solve_one_callback,
-        solve_two_callback,
+        cancel_callback,
     }: &RunButtonProps,
 ) -> Html {
-    let day = *day;
-    let input = input.clone();
-    let run_cb = run_callback.clone();
-    let solve_one_cb = solve_one_callback.clone();
-    let solve_two_cb = solve_two_callback.clone();
-
-    let solve1 = use_oneshot_runner::<SolveOneTask>();
-    let solve2 = use_oneshot_runner::<SolveTwoTask>();
-
-    let run = move |_: MouseEvent| {
-        run_cb.emit(());
+    let timeout = use_state(|| None);
+    let runs = use_state(|| 1);
+    let explain = use_state(|| false);
+    let log = use_state(|| None);
 
-        {
-            let solve2 = solve2.clone();
-            let input = input.clone();
-            let solve_two_cb = solve_two_cb.clone();
-            spawn_local(async move {
-                let res = solve2.run((day, input)).await;
-                solve_two_cb.emit(res);
-            });
-        }
-        {
-            let solve1 = solve1.clone();
-            let input = input.clone();
-            let solve_one_cb = solve_one_cb.clone();
-            spawn_local(async move {
-                let res = solve1.run((day, input)).await;
-                solve_one_cb.emit(res);
-            });
-        }
+    let on_run = {
+        let timeout = timeout.clone();
+        let runs = runs.clone();
+        let explain = explain.clone();
+        let log = log.clone();
+        let run_cb = run_callback.clone();
+        Callback::from(move |_: MouseEvent| {
+            run_cb.emit(RunOptions {
+                timeout: *timeout,
+                runs: *runs,
+                explain: *explain,
+                log: *log,
+            })
+        })
     };
+    let on_cancel = {
+        let cancel_cb = cancel_callback.clone();
+        Callback::from(move |_: MouseEvent| cancel_cb.emit(()))
+    };
+    let on_timeout = {
+        let timeout = timeout.clone();
+        Callback::from(move |event: Event| {
+            let select: HtmlSelectElement = event.target_unchecked_into();
+            timeout.set(select.value().parse::<u32>().ok());
+        })
+    };
+    let on_runs = {
+        let runs = runs.clone();
+        Callback::from(move |event: Event| {
+            let select: HtmlSelectElement = event.target_unchecked_into();
+            runs.set(select.value().parse::<u32>().unwrap_or(1));
+        })
+    };
+    let on_explain = {
+        let explain = explain.clone();
+        Callback::from(move |event: Event| {
+            let input: HtmlInputElement = event.target_unchecked_into();
+            explain.set(input.checked());
+        })
+    };
+    let on_log = {
+        let log = log.clone();
+        Callback::from(move |event: Event| {
+            let select: HtmlSelectElement = event.target_unchecked_into();
+            log.set(
+                select
+                    .value()
+                    .parse::<usize>()
+                    .ok()
+                    .and_then(|i| LOG_LEVELS.get(i).copied().flatten()),
+            );
+        })
+    };
+    let on_part = {
+        let part_cb = part_callback.clone();
+        Callback::from(move |event: Event| {
+            let select: HtmlSelectElement = event.target_unchecked_into();
+            part_cb.emit(select.value().parse::<u8>().ok());
+        })
+    };
+
+    let part_options = PARTS
+        .iter()
+        .map(|p| {
+            let (value, label) = p
+                .map(|p| (p.to_string(), format!("part {}", p)))
+                .unwrap_or_else(|| (String::default(), "both".to_string()));
+            html! {
+                <option value={value} selected={*p == *part}>{label}</option>
+            }
+        })
+        .collect::<Html>();
+    let options = TIMEOUTS
+        .iter()
+        .map(|t| {
+            let (value, label) = t
+                .map(|s| (s.to_string(), format!("{}s", s)))
+                .unwrap_or_else(|| (String::default(), "none".to_string()));
+            html! {
+                <option value={value} selected={*t == *timeout}>{label}</option>
+            }
+        })
+        .collect::<Html>();
+    let runs_options = RUNS
+        .iter()
+        .map(|r| {
+            html! {
+                <option value={r.to_string()} selected={*r == *runs}>{r}</option>
+            }
+        })
+        .collect::<Html>();
+    let log_options = LOG_LEVELS
+        .iter()
+        .enumerate()
+        .map(|(i, l)| {
+            let label = l
+                .map(|l| l.to_string().to_lowercase())
+                .unwrap_or_else(|| "off".to_string());
+            html! {
+                <option value={i.to_string()} selected={*l == *log}>{label}</option>
+            }
+        })
+        .collect::<Html>();
 
-    let on_click = run.clone();
     html! {
-        <button
-            onclick={on_click}
-            type="button">{"Run"}
-        </button>
+        <>
+            <button
+                onclick={on_run}
+                type="button">{"Run"}
+            </button>
+            <button
+                onclick={on_cancel}
+                disabled={!*running}
+                type="button">{"Cancel"}
+            </button>
+            <label>
+                {"Part "}
+                <select onchange={on_part}>{part_options}</select>
+            </label>
+            <label>
+                {"Timeout "}
+                <select onchange={on_timeout}>{options}</select>
+            </label>
+            <label>
+                {"Runs "}
+                <select onchange={on_runs}>{runs_options}</select>
+            </label>
+            <label>
+                <input type="checkbox" checked={*explain} onchange={on_explain}/>
+                {" Explain"}
+            </label>
+            <label>
+                {"Log "}
+                <select onchange={on_log}>{log_options}</select>
+            </label>
+        </>
     }
 }
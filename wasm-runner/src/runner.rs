@@ -1,21 +1,143 @@
-use crate::agent::{SolveOneTask, SolveTwoTask};
-use crate::run_button::RunButton;
-use web_sys::{HtmlTextAreaElement, InputEvent};
+use crate::agent::{
+    Solution, SolveCallbacks, SolveError, SolveJob, SolveProgress, SolveRequest, SolveSection,
+    SolveTimes, VisualizeRequest, WorkerPool,
+};
+use crate::answers;
+use crate::data;
+use crate::file_input::FileInput;
+use crate::route::{self, Route};
+use crate::run_button::{RunButton, RunOptions};
+use crate::storage::{self, SavedDay, SavedOutput};
+use crate::visualization::Visualization;
+use std::rc::Rc;
+use utils::visualize::Frames;
+use web_sys::{HtmlInputElement, HtmlTextAreaElement, InputEvent};
 use yew::prelude::*;
-use yew_agent::oneshot::OneshotProvider;
 
 #[derive(Debug)]
 enum Output {
-    Calculating(usize),
-    Error(String),
-    Solution(String, i64),
+    Calculating(usize, Option<SolveProgress>),
+    Cancelled,
+    Skipped,
+    Error(SolveError),
+    Solution(Solution),
 }
+
+/// Collapsible explanation of a solve, empty if it was not explained.
+fn explanation_html(explanation: &[SolveSection]) -> Html {
+    if explanation.is_empty() {
+        return html! {};
+    }
+    html! {
+        <details>
+            <summary>{"explanation"}</summary>
+            { for explanation.iter().map(|section| html! {
+                <>
+                    <b>{&section.title}</b>
+                    <pre>{section.lines.join("\n")}</pre>
+                </>
+            })}
+        </details>
+    }
+}
+
+/// Error with its causes collapsed below it, and a button to select the failing line of the input.
+fn error_html(err: &SolveError, on_show_line: &Callback<usize>) -> Html {
+    html! {
+        <>
+            {"ERROR - "}{&err.message}
+            if let Some((line, _)) = err.location {
+                {" "}
+                <button
+                    onclick={on_show_line.reform(move |_| line)}
+                    type="button">{format!("show line {}", line)}
+                </button>
+            }
+            if !err.chain.is_empty() {
+                <details>
+                    <summary>{"caused by"}</summary>
+                    <ol>{for err.chain.iter().map(|cause| html! {<li>{cause}</li>})}</ol>
+                </details>
+            }
+        </>
+    }
+}
+
 impl Output {
-    fn to_html(&self) -> Html {
+    /// `known` is the known correct answer the solution is checked against.
+    fn to_html(&self, known: Option<&str>, on_show_line: &Callback<usize>) -> Html {
+        match self {
+            Self::Calculating(count, progress) => html! {
+                <>
+                    {"calculating"}{vec!['.'; *count]}
+                    if let Some(p) = progress {
+                        <br/>
+                        if let Some(total) = p.total {
+                            <progress value={p.done.to_string()} max={total.to_string()}/>
+                            {format!(" {}/{}", p.done, total)}
+                        } else {
+                            <progress/>
+                            {format!(" {}", p.done)}
+                        }
+                        if let Some(value) = &p.value {
+                            <br/>{value}
+                        }
+                    }
+                </>
+            },
+            Self::Cancelled => html! {<>{"cancelled"}</>},
+            Self::Skipped => html! {<>{"not run"}</>},
+            Self::Error(err) => error_html(err, on_show_line),
+            Self::Solution(sol) => html! {
+                <>
+                    {&sol.answer}{answers::check_html(known, &sol.answer)}
+                    <br/>{sol.summary()}
+                    {explanation_html(&sol.explanation)}
+                </>
+            },
+        }
+    }
+
+    fn stop(&mut self, stopped: impl FnOnce() -> Output) {
+        if let Self::Calculating(..) = self {
+            *self = stopped();
+        }
+    }
+
+    fn progress(&mut self, progress: SolveProgress) -> bool {
+        if let Self::Calculating(_, p) = self {
+            *p = Some(progress);
+            true
+        } else {
+            false
+        }
+    }
+
+    fn to_saved(&self) -> SavedOutput {
         match self {
-            Self::Calculating(count) => html! {<>{"calculating"}{vec!['.'; *count]}</>},
-            Self::Error(err) => html! {<>{"ERROR - "}{err}</>},
-            Self::Solution(sol, ms) => html! {<>{sol}<br/>{ms}{"ms"}</>},
+            // a reload stops the run, so save calculating as cancelled
+            Self::Calculating(..) | Self::Cancelled => SavedOutput::Cancelled,
+            Self::Skipped => SavedOutput::Skipped,
+            Self::Error(err) => SavedOutput::Error(err.to_string()),
+            Self::Solution(sol) => SavedOutput::Solution(sol.answer.clone(), sol.ms()),
+        }
+    }
+}
+impl From<SavedOutput> for Output {
+    fn from(saved: SavedOutput) -> Self {
+        match saved {
+            SavedOutput::Cancelled => Self::Cancelled,
+            SavedOutput::Skipped => Self::Skipped,
+            SavedOutput::Error(err) => Self::Error(err.into()),
+            // only the median time is saved, so it is restored as a single run
+            SavedOutput::Solution(answer, ms) => Self::Solution(Solution {
+                answer,
+                times: vec![SolveTimes {
+                    parse: None,
+                    total: ms,
+                }],
+                explanation: vec![],
+            }),
         }
     }
 }
@@ -23,87 +145,211 @@ impl Output {
 #[derive(Debug)]
 pub enum Msg {
     InputUpdate(String),
-    Run,
-    Tick,
-    OkOne(String, i64),
-    ErrOne(String),
-    OkTwo(String, i64),
-    ErrTwo(String),
+    Clear,
+    Share,
+    Visualize,
+    Visualized(Result<Frames, SolveError>),
+    ShowLine(usize),
+    Answer(u8, String),
+    Run(RunOptions),
+    Cancel,
+    Timeout(usize, u32),
+    Tick(usize),
+    ProgressOne(SolveProgress),
+    ProgressTwo(SolveProgress),
+    OkOne(Solution),
+    ErrOne(SolveError),
+    OkTwo(Solution),
+    ErrTwo(SolveError),
 }
 
 #[derive(Properties, PartialEq)]
 pub struct RunnerProps {
     pub day: u8,
+    /// part to run, `None` runs both
+    pub part: Option<u8>,
+    pub on_part_select: Callback<Option<u8>>,
 }
 
 pub struct Runner {
     input: String,
     output: Option<(Output, Output)>,
+    // known correct answers for each part
+    answers: (Option<String>, Option<String>),
+    // link to the day with the input in the fragment, or why it could not be made
+    share: Option<Result<String, String>>,
+    pool: WorkerPool,
+    // jobs for each part, dropping a job cancels it
+    jobs: (Option<SolveJob>, Option<SolveJob>),
+    // frames of the day's visualization, and the job making them while it runs
+    visualization: Option<Result<Rc<Frames>, SolveError>>,
+    visualize_job: Option<SolveJob>,
+    textarea: NodeRef,
+    // incremented every run, so ticks and timeouts from earlier runs are ignored
+    run: usize,
+}
+impl Runner {
+    fn running(&self) -> bool {
+        self.jobs.0.is_some() || self.jobs.1.is_some()
+    }
+
+    fn stop(&mut self, stopped: impl Fn() -> Output) {
+        self.jobs = (None, None);
+        if let Some((o1, o2)) = self.output.as_mut() {
+            o1.stop(&stopped);
+            o2.stop(&stopped);
+        }
+    }
+
+    fn load(&mut self, day: u8) {
+        let saved = storage::load(day);
+        self.input = saved.input;
+        self.output = saved.output.map(|(o1, o2)| (o1.into(), o2.into()));
+        self.answers = saved.answers;
+    }
+
+    fn save(&self, day: u8) {
+        storage::save(
+            day,
+            &SavedDay {
+                input: self.input.clone(),
+                output: self
+                    .output
+                    .as_ref()
+                    .map(|(o1, o2)| (o1.to_saved(), o2.to_saved())),
+                answers: self.answers.clone(),
+            },
+        );
+    }
+
+    fn tick(ctx: &Context<Self>, run: usize) {
+        ctx.link().send_future(async move {
+            yew::platform::time::sleep(std::time::Duration::from_secs(1)).await;
+            Msg::Tick(run)
+        });
+    }
 }
 
 impl Component for Runner {
     type Message = Msg;
     type Properties = RunnerProps;
 
-    fn create(_ctx: &Context<Self>) -> Self {
-        Self {
+    fn create(ctx: &Context<Self>) -> Self {
+        let mut runner = Self {
             input: String::default(),
             output: None,
+            answers: (None, None),
+            share: None,
+            pool: ctx
+                .link()
+                .context::<WorkerPool>(Callback::noop())
+                .expect("worker pool context")
+                .0,
+            jobs: (None, None),
+            visualization: None,
+            visualize_job: None,
+            textarea: NodeRef::default(),
+            run: 0,
+        };
+        let day = ctx.props().day;
+        runner.load(day);
+        // a shared link replaces the saved input
+        if let Some(input) = route::take_shared_input() {
+            runner.input = input;
+            runner.output = None;
+            // known answers are for the replaced input
+            runner.answers = (None, None);
+            runner.save(day);
         }
+        runner
     }
 
     fn view(&self, ctx: &Context<Self>) -> Html {
         let day = ctx.props().day;
-
-        let run_callback = {
-            let link = ctx.link().clone();
-            Callback::from(move |_| {
-                link.send_message(Msg::Run);
-            })
-        };
-
-        let solve_one_callback = {
-            let link = ctx.link().clone();
-            Callback::from(move |res: Result<(String, i64), String>| {
-                link.send_message(
-                    res.map(|(s, t)| Msg::OkOne(s, t))
-                        .unwrap_or_else(Msg::ErrOne),
-                );
-            })
-        };
-
-        let solve_two_callback = {
-            let link = ctx.link().clone();
-            Callback::from(move |res: Result<(String, i64), String>| {
-                link.send_message(
-                    res.map(|(s, t)| Msg::OkTwo(s, t))
-                        .unwrap_or_else(Msg::ErrTwo),
-                );
-            })
+        let on_show_line = ctx.link().callback(Msg::ShowLine);
+        let known_answer = |part: u8, known: &Option<String>| {
+            html! {
+                <label>
+                    {format!("Known answer part {} ", part)}
+                    <input
+                        type="text"
+                        value={known.clone().unwrap_or_default()}
+                        onchange={ctx.link().callback(move |event: Event| {
+                            let input: HtmlInputElement = event.target_unchecked_into();
+                            Msg::Answer(part, input.value())
+                        })}/>
+                </label>
+            }
         };
 
         html! {
             <div>
                 <h3>{ format!("Day {}", day) }</h3>
-                <OneshotProvider<SolveOneTask> path="/aoc_2023/worker1.js">
-                    <OneshotProvider<SolveTwoTask> path="/aoc_2023/worker2.js">
-                        <RunButton
-                            day={day}
-                            input={self.input.clone()}
-                            run_callback={run_callback}
-                            solve_one_callback={solve_one_callback}
-                            solve_two_callback={solve_two_callback}/>
-                    </OneshotProvider<SolveTwoTask>>
-                </OneshotProvider<SolveOneTask>>
+                <RunButton
+                    running={self.running()}
+                    part={ctx.props().part}
+                    part_callback={ctx.props().on_part_select.clone()}
+                    run_callback={ctx.link().callback(Msg::Run)}
+                    cancel_callback={ctx.link().callback(|_| Msg::Cancel)}/>
                 if let Some((o1, o2)) = &self.output {
                     <p>
-                        <b>{"Part One: "}</b>{o1.to_html()}
+                        <b>{"Part One: "}</b>
+                        {o1.to_html(self.answers.0.as_deref(), &on_show_line)}
                         <br />
-                        <b>{"Part Two: "}</b>{o2.to_html()}
+                        <b>{"Part Two: "}</b>
+                        {o2.to_html(self.answers.1.as_deref(), &on_show_line)}
                     </p>
                 }
+                <details open={self.answers.0.is_some() || self.answers.1.is_some()}>
+                    <summary>{"Known answers"}</summary>
+                    {known_answer(1, &self.answers.0)}
+                    {known_answer(2, &self.answers.1)}
+                </details>
+                if data::visualizer(day).is_some() {
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::Visualize)}
+                        disabled={self.input.is_empty() || self.visualize_job.is_some()}
+                        type="button">{"Visualize"}
+                    </button>
+                    if self.visualize_job.is_some() {
+                        <p>{"rendering..."}</p>
+                    } else if let Some(visualization) = &self.visualization {
+                        {match visualization {
+                            Ok(frames) => html! {<Visualization frames={frames.clone()}/>},
+                            Err(err) => html! {<p>{error_html(err, &on_show_line)}</p>},
+                        }}
+                    }
+                }
                 <p>{"Enter puzzle input"}</p>
+                <FileInput on_load={ctx.link().callback(Msg::InputUpdate)}/>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::Clear)}
+                    type="button">{"Clear"}
+                </button>
+                <button
+                    onclick={ctx.link().callback(|_| Msg::Share)}
+                    disabled={self.input.is_empty()}
+                    type="button">{"Share"}
+                </button>
+                if let Some(share) = &self.share {
+                    <p>
+                        {match share {
+                            Ok(link) => html! {<input type="text" readonly=true value={link.clone()}/>},
+                            Err(err) => html! {<>{"ERROR - "}{err}</>},
+                        }}
+                    </p>
+                }
+                if !self.input.is_empty() {
+                    <p>
+                        {format!(
+                            "{} lines, {} bytes",
+                            self.input.lines().count(),
+                            self.input.len()
+                        )}
+                    </p>
+                }
                 <textarea
+                    ref={self.textarea.clone()}
                     value={self.input.clone()}
                     oninput={ctx.link().callback(|event: InputEvent| {
                         let input: HtmlTextAreaElement = event.target_unchecked_into();
@@ -116,51 +362,199 @@ impl Component for Runner {
     }
 
     fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
-        match msg {
+        // keep input and finished results saved for when the day is reselected or page reloaded
+        let save = matches!(
+            msg,
+            Msg::InputUpdate(_)
+                | Msg::Answer(..)
+                | Msg::Cancel
+                | Msg::Timeout(..)
+                | Msg::OkOne(..)
+                | Msg::ErrOne(_)
+                | Msg::OkTwo(..)
+                | Msg::ErrTwo(_)
+        );
+        let updated = match msg {
             Msg::InputUpdate(input) => {
                 self.input = input;
+                self.share = None;
+                true
+            }
+            Msg::Clear => {
+                storage::clear(ctx.props().day);
+                self.input = String::default();
+                self.output = None;
+                self.answers = (None, None);
+                self.share = None;
+                self.visualization = None;
+                self.visualize_job = None;
+                self.jobs = (None, None);
+                true
+            }
+            Msg::Share => {
+                let route = Route::Day {
+                    day: ctx.props().day,
+                    part: ctx.props().part,
+                };
+                self.share = Some(route::share_link(&route, &self.input));
+                true
+            }
+            Msg::Visualize => {
+                let request = VisualizeRequest {
+                    day: ctx.props().day,
+                    input: self.input.clone(),
+                };
+                let on_visualized = ctx.link().callback(Msg::Visualized);
+                self.visualize_job = Some(self.pool.visualize(request, on_visualized));
+                true
+            }
+            Msg::Visualized(res) => {
+                self.visualize_job = None;
+                self.visualization = Some(res.map(Rc::new));
+                true
+            }
+            Msg::Answer(part, answer) => {
+                let answer = Some(answer.trim().to_string()).filter(|a| !a.is_empty());
+                if part == 1 {
+                    self.answers.0 = answer;
+                } else {
+                    self.answers.1 = answer;
+                }
                 true
             }
-            Msg::Run => {
-                self.output = Some((Output::Calculating(1), Output::Calculating(1)));
-                ctx.link().send_future(async move {
-                    yew::platform::time::sleep(std::time::Duration::from_secs(1)).await;
-                    Msg::Tick
-                });
+            Msg::ShowLine(line) => {
+                if let Some(textarea) = self.textarea.cast::<HtmlTextAreaElement>() {
+                    // selection offsets are in UTF-16 code units
+                    let len = |s: &str| s.encode_utf16().count() as u32;
+                    let index = line.saturating_sub(1);
+                    let start = self.input.split_inclusive('\n').take(index).map(len).sum();
+                    let text = self.input.lines().nth(index).unwrap_or_default();
+                    let _ = textarea.focus();
+                    let _ = textarea.set_selection_range(start, start + len(text));
+                    // the selection is not scrolled to, so centre the line as well as it can be
+                    // without knowing the line height
+                    let lines = self.input.lines().count().max(1) as i32;
+                    let top = textarea.scroll_height() * index as i32 / lines;
+                    textarea.set_scroll_top(top - textarea.client_height() / 2);
+                }
+                false
+            }
+            Msg::Run(options) => {
+                let day = ctx.props().day;
+                let runs = |part| ctx.props().part.is_none_or(|p| p == part);
+                self.run += 1;
+                let submit = |part, on_progress, on_solved| {
+                    if !runs(part) {
+                        return None;
+                    }
+                    let request = SolveRequest {
+                        day,
+                        part,
+                        variant: None,
+                        input: self.input.clone(),
+                        runs: options.runs,
+                        explain: options.explain,
+                        log: options.log,
+                    };
+                    let callbacks = SolveCallbacks {
+                        on_start: Callback::noop(),
+                        on_progress,
+                        on_solved,
+                    };
+                    Some(self.pool.submit(request, callbacks))
+                };
+                // replacing any running jobs cancels them
+                self.jobs = (
+                    submit(
+                        1,
+                        ctx.link().callback(Msg::ProgressOne),
+                        ctx.link().callback(|res: Result<Solution, SolveError>| {
+                            res.map(Msg::OkOne).unwrap_or_else(Msg::ErrOne)
+                        }),
+                    ),
+                    submit(
+                        2,
+                        ctx.link().callback(Msg::ProgressTwo),
+                        ctx.link().callback(|res: Result<Solution, SolveError>| {
+                            res.map(Msg::OkTwo).unwrap_or_else(Msg::ErrTwo)
+                        }),
+                    ),
+                );
+                let output = |part| {
+                    if runs(part) {
+                        Output::Calculating(1, None)
+                    } else {
+                        Output::Skipped
+                    }
+                };
+                self.output = Some((output(1), output(2)));
+                Self::tick(ctx, self.run);
+                if let Some(secs) = options.timeout {
+                    let run = self.run;
+                    ctx.link().send_future(async move {
+                        yew::platform::time::sleep(std::time::Duration::from_secs(secs.into()))
+                            .await;
+                        Msg::Timeout(run, secs)
+                    });
+                }
 
                 true
             }
-            Msg::Tick => {
+            Msg::Cancel => {
+                self.stop(|| Output::Cancelled);
+                true
+            }
+            Msg::Timeout(run, secs) => {
+                if run == self.run && self.running() {
+                    self.stop(|| Output::Error(format!("timed out after {}s", secs).into()));
+                    true
+                } else {
+                    false
+                }
+            }
+            Msg::Tick(run) => {
+                if run != self.run {
+                    return false;
+                }
                 let mut updated = false;
                 if let Some((o1, o2)) = self.output.as_mut() {
-                    if let Output::Calculating(c) = o1 {
+                    if let Output::Calculating(c, _) = o1 {
                         *c = (*c % 3) + 1;
                         updated = true;
                     }
-                    if let Output::Calculating(c) = o2 {
+                    if let Output::Calculating(c, _) = o2 {
                         *c = (*c % 3) + 1;
                         updated = true;
                     }
                 }
 
                 if updated {
-                    ctx.link().send_future(async move {
-                        yew::platform::time::sleep(std::time::Duration::from_secs(1)).await;
-                        Msg::Tick
-                    });
+                    Self::tick(ctx, run);
                 }
 
                 updated
             }
-            Msg::OkOne(sol, ms) => {
+            Msg::ProgressOne(progress) => self
+                .output
+                .as_mut()
+                .map(|(o1, _)| o1.progress(progress))
+                .unwrap_or(false),
+            Msg::ProgressTwo(progress) => self
+                .output
+                .as_mut()
+                .map(|(_, o2)| o2.progress(progress))
+                .unwrap_or(false),
+            Msg::OkOne(sol) => {
+                self.jobs.0 = None;
                 if let Some((o1, _)) = self.output.as_mut() {
-                    *o1 = Output::Solution(sol, ms);
+                    *o1 = Output::Solution(sol);
                     true
                 } else {
                     false
                 }
             }
             Msg::ErrOne(err) => {
+                self.jobs.0 = None;
                 if let Some((o1, _)) = self.output.as_mut() {
                     *o1 = Output::Error(err);
                     true
@@ -168,15 +562,17 @@ impl Component for Runner {
                     false
                 }
             }
-            Msg::OkTwo(sol, ms) => {
+            Msg::OkTwo(sol) => {
+                self.jobs.1 = None;
                 if let Some((_, o2)) = self.output.as_mut() {
-                    *o2 = Output::Solution(sol, ms);
+                    *o2 = Output::Solution(sol);
                     true
                 } else {
                     false
                 }
             }
             Msg::ErrTwo(err) => {
+                self.jobs.1 = None;
                 if let Some((_, o2)) = self.output.as_mut() {
                     *o2 = Output::Error(err);
                     true
@@ -184,15 +580,22 @@ impl Component for Runner {
                     false
                 }
             }
+        };
+        if save && updated {
+            self.save(ctx.props().day);
         }
+        updated
     }
 
     fn changed(&mut self, ctx: &Context<Self>, old_props: &Self::Properties) -> bool {
         if ctx.props().day == old_props.day {
-            false
+            ctx.props().part != old_props.part
         } else {
-            self.input = String::default();
-            self.output = None;
+            self.share = None;
+            self.visualization = None;
+            self.visualize_job = None;
+            self.jobs = (None, None);
+            self.load(ctx.props().day);
             true
         }
     }
@@ -0,0 +1,51 @@
+use gloo_storage::{LocalStorage, Storage};
+use serde::{Deserialize, Serialize};
+
+/// Last answer or error for a part, kept in browser local storage.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub enum SavedOutput {
+    Cancelled,
+    Skipped,
+    Error(String),
+    /// answer and median run time in milliseconds
+    Solution(String, f64),
+}
+
+/// Input and last results for a day, kept in browser local storage.
+#[derive(Clone, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub struct SavedDay {
+    pub input: String,
+    pub output: Option<(SavedOutput, SavedOutput)>,
+    /// known correct answers for the input, results are checked against them
+    #[serde(default)]
+    pub answers: (Option<String>, Option<String>),
+}
+
+fn key(day: u8) -> String {
+    format!("aoc_2023/day{}", day)
+}
+
+/// Saved input and results for the day, or empty if nothing saved or it cannot be read.
+pub fn load(day: u8) -> SavedDay {
+    LocalStorage::get(key(day)).unwrap_or_default()
+}
+
+pub fn save(day: u8, saved: &SavedDay) {
+    // storage can be full or disabled, losing the saved day is not worth failing the run over
+    let _ = LocalStorage::set(key(day), saved);
+}
+
+pub fn clear(day: u8) {
+    LocalStorage::delete(key(day));
+}
+
+const POOL_SIZE_KEY: &str = "aoc_2023/pool_size";
+
+/// Number of solver workers picked by the user, if they have changed it from the default.
+pub fn load_pool_size() -> Option<usize> {
+    LocalStorage::get(POOL_SIZE_KEY).ok()
+}
+
+pub fn save_pool_size(size: usize) {
+    let _ = LocalStorage::set(POOL_SIZE_KEY, size);
+}
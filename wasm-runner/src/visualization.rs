@@ -0,0 +1,169 @@
+use std::rc::Rc;
+use utils::visualize::Frames;
+use wasm_bindgen::JsCast;
+use web_sys::{CanvasRenderingContext2d, HtmlCanvasElement, HtmlInputElement};
+use yew::prelude::*;
+
+/// Largest side of the canvas in pixels, cells are scaled to fit.
+const MAX_CANVAS_SIZE: usize = 600;
+/// Time each frame is shown for when playing.
+const FRAME_MS: u64 = 100;
+
+#[derive(Debug)]
+pub enum Msg {
+    Play,
+    Pause,
+    Seek(usize),
+    Tick(usize),
+}
+
+#[derive(Properties, PartialEq)]
+pub struct VisualizationProps {
+    pub frames: Rc<Frames>,
+}
+
+/// Canvas showing a frame of a grid visualization, with controls to play through the frames.
+pub struct Visualization {
+    canvas: NodeRef,
+    frame: usize,
+    playing: bool,
+    // incremented every play, so ticks from earlier plays are ignored
+    play: usize,
+}
+impl Visualization {
+    fn tick(ctx: &Context<Self>, play: usize) {
+        ctx.link().send_future(async move {
+            yew::platform::time::sleep(std::time::Duration::from_millis(FRAME_MS)).await;
+            Msg::Tick(play)
+        });
+    }
+
+    fn cell_size(frames: &Frames) -> usize {
+        (MAX_CANVAS_SIZE / frames.width.max(frames.height).max(1)).max(1)
+    }
+
+    fn draw(&self, frames: &Frames) -> Option<()> {
+        let canvas = self.canvas.cast::<HtmlCanvasElement>()?;
+        let context = canvas
+            .get_context("2d")
+            .ok()??
+            .dyn_into::<CanvasRenderingContext2d>()
+            .ok()?;
+        let cells = frames.frames.get(self.frame)?;
+        let size = Self::cell_size(frames) as f64;
+
+        // one fill style per colour rather than per cell
+        for (value, colour) in frames.palette.iter().enumerate() {
+            context.set_fill_style_str(colour);
+            for (i, _) in cells
+                .iter()
+                .enumerate()
+                .filter(|(_, &c)| c as usize == value)
+            {
+                let (row, col) = (i / frames.width, i % frames.width);
+                context.fill_rect(col as f64 * size, row as f64 * size, size, size);
+            }
+        }
+        Some(())
+    }
+}
+
+impl Component for Visualization {
+    type Message = Msg;
+    type Properties = VisualizationProps;
+
+    fn create(ctx: &Context<Self>) -> Self {
+        // start on the final state, intermediate frames are played on request
+        Self {
+            canvas: NodeRef::default(),
+            frame: ctx.props().frames.frames.len().saturating_sub(1),
+            playing: false,
+            play: 0,
+        }
+    }
+
+    fn view(&self, ctx: &Context<Self>) -> Html {
+        let frames = &ctx.props().frames;
+        let size = Self::cell_size(frames);
+        let last = frames.frames.len().saturating_sub(1);
+
+        html! {
+            <div>
+                <canvas
+                    ref={self.canvas.clone()}
+                    width={(frames.width * size).to_string()}
+                    height={(frames.height * size).to_string()}/>
+                <br/>
+                if self.playing {
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::Pause)}
+                        type="button">{"Pause"}
+                    </button>
+                } else {
+                    <button
+                        onclick={ctx.link().callback(|_| Msg::Play)}
+                        disabled={last == 0}
+                        type="button">{"Play"}
+                    </button>
+                }
+                <input
+                    type="range"
+                    min="0"
+                    max={last.to_string()}
+                    value={self.frame.to_string()}
+                    oninput={ctx.link().callback(|event: InputEvent| {
+                        let input: HtmlInputElement = event.target_unchecked_into();
+                        Msg::Seek(input.value().parse().unwrap_or_default())
+                    })}/>
+                {format!(" frame {}/{}", self.frame + 1, last + 1)}
+            </div>
+        }
+    }
+
+    fn update(&mut self, ctx: &Context<Self>, msg: Self::Message) -> bool {
+        let last = ctx.props().frames.frames.len().saturating_sub(1);
+        match msg {
+            Msg::Play => {
+                // play from the start once at the end
+                if self.frame == last {
+                    self.frame = 0;
+                }
+                self.playing = true;
+                self.play += 1;
+                Self::tick(ctx, self.play);
+                true
+            }
+            Msg::Pause => {
+                self.playing = false;
+                true
+            }
+            Msg::Seek(frame) => {
+                self.playing = false;
+                self.frame = frame.min(last);
+                true
+            }
+            Msg::Tick(play) => {
+                if play != self.play || !self.playing {
+                    return false;
+                }
+                if self.frame < last {
+                    self.frame += 1;
+                    Self::tick(ctx, play);
+                } else {
+                    self.playing = false;
+                }
+                true
+            }
+        }
+    }
+
+    fn changed(&mut self, ctx: &Context<Self>, _old_props: &Self::Properties) -> bool {
+        self.playing = false;
+        self.frame = ctx.props().frames.frames.len().saturating_sub(1);
+        true
+    }
+
+    fn rendered(&mut self, ctx: &Context<Self>, _first_render: bool) {
+        self.draw(&ctx.props().frames);
+    }
+}